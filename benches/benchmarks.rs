@@ -1,30 +1,66 @@
+use aoc22::answers::input_dir;
+use aoc22::dijkstra::{bfs, bfs_dense, dijkstra, dijkstra_dense};
+use aoc22::grid::{Grid, Pos};
+use aoc22::solution::Context;
 use criterion::{criterion_group, criterion_main, Criterion};
+use std::path::Path;
 
-macro_rules! benchmark {
-    ($name: ident) => {
-        fn $name(c: &mut Criterion) {
-            let input = std::fs::read_to_string(format!(
-                "{}/inputs/{}",
-                env!("CARGO_MANIFEST_DIR"),
-                stringify!($name)
-            ))
-            .unwrap();
-            c.bench_function(stringify!($name), |b| {
-                b.iter(|| aoc22::$name::solve(&input, false, false))
-            });
-        }
-    };
+fn benchmarks(c: &mut Criterion) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for day in aoc22::registry::years().flat_map(|year| aoc22::year(year).unwrap()) {
+        let name = day.name();
+        let input = std::fs::read_to_string(root.join(input_dir(day.year)).join(&name)).unwrap();
+        let parsed = (day.parse)(&input).unwrap();
+        let ctx = Context::default();
+        let mut group = c.benchmark_group(format!("{}_{name}", day.year));
+        group.bench_function("parse", |b| b.iter(|| (day.parse)(&input)));
+        group.bench_function("part1", |b| b.iter(|| (day.part1)(&*parsed, &ctx)));
+        group.bench_function("part2", |b| b.iter(|| (day.part2)(&*parsed, &ctx)));
+        group.finish();
+    }
 }
 
-macro_rules! benchmarks {
-    ($($name:ident),+) => {
-        $(
-            benchmark!{$name}
-        )+
+// Searches on the hash map and the Vec based storage over the day 12 heightmap, backwards from
+// the target to every square, like its part 2.
+fn search_benchmarks(c: &mut Criterion) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(input_dir(2022))
+        .join("day12");
+    let input = std::fs::read_to_string(path).unwrap();
+    let m = Grid::parse(&input, |c| {
+        Ok(match c {
+            'S' => b'a',
+            'E' => b'z',
+            c => c as u8,
+        })
+    })
+    .unwrap();
+    let target = Grid::parse(&input, Ok)
+        .unwrap()
+        .position(|c| *c == 'E')
+        .unwrap();
+    let grid = &m;
+    let neighbours_of = |p: &Pos| {
+        let p = *p;
+        grid.neighbours4(p)
+            .filter(move |next| grid[p] <= grid[*next] + 1)
+    };
 
-        criterion_group!(benches, $($name,)+);
-        criterion_main!(benches);
-    }
+    let mut group = c.benchmark_group("day12_search");
+    group.bench_function("bfs", |b| {
+        b.iter(|| bfs(target, |_| false, |p| neighbours_of(p).collect()))
+    });
+    group.bench_function("bfs_dense", |b| {
+        b.iter(|| bfs_dense(target, &m, |_| false, neighbours_of))
+    });
+    group.bench_function("dijkstra", |b| {
+        b.iter(|| dijkstra(target, |p| neighbours_of(p).map(|n| (n, 1)).collect()))
+    });
+    group.bench_function("dijkstra_dense", |b| {
+        b.iter(|| dijkstra_dense(target, &m, |p| neighbours_of(p).map(|n| (n, 1))))
+    });
+    group.finish();
 }
 
-benchmarks! {day01,day02,day03,day04,day05,day06,day07,day08,day09,day10,day11,day12,day13,day14,day15,day16,day17,day18,day19,day20,day21,day22,day23,day24,day25}
+criterion_group!(benches, benchmarks, search_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,267 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Error, Formatter};
+use std::path::{Path, PathBuf};
+
+use crate::solution::{Answer, Answers, Params};
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Value {
+    Num(i64),
+    Text(String),
+}
+
+impl From<Value> for Answer {
+    fn from(v: Value) -> Self {
+        match v {
+            Value::Num(n) => Answer::Num(n),
+            Value::Text(s) if s.contains('\n') => Answer::Multiline(s),
+            Value::Text(s) => Answer::Text(s),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawExpected {
+    part1: Option<Value>,
+    part2: Option<Value>,
+    #[serde(default)]
+    params: Params,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    // Passed to the solution along with the input, like the row day 15's example asks about.
+    pub params: Params,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub part: usize,
+    pub expected: Answer,
+    pub actual: Answer,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(
+            f,
+            "part {}: expected '{}', got '{}'",
+            self.part, self.expected, self.actual
+        )
+    }
+}
+
+impl Expected {
+    pub fn check(&self, answers: &Answers) -> Vec<Mismatch> {
+        [
+            (1, &self.part1, &answers.part1),
+            (2, &self.part2, &answers.part2),
+        ]
+        .into_iter()
+        .filter_map(|(part, expected, actual)| match expected {
+            Some(expected) if expected != actual => Some(Mismatch {
+                part,
+                expected: expected.clone(),
+                actual: actual.clone(),
+            }),
+            _ => None,
+        })
+        .collect()
+    }
+}
+
+// Expected answers per day, keyed by input name - file name without extension, like
+// 'day01' or 'day09_example2'.
+#[derive(Debug, Clone, Default)]
+pub struct Manifest {
+    days: BTreeMap<usize, BTreeMap<String, Expected>>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let s = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read '{}'", path.display()))?;
+        Self::parse(&s).with_context(|| format!("failed to parse '{}'", path.display()))
+    }
+
+    pub fn parse(s: &str) -> Result<Self> {
+        let raw: BTreeMap<String, BTreeMap<String, RawExpected>> = toml::from_str(s)?;
+        let mut days = BTreeMap::new();
+        for (day, inputs) in raw {
+            let day: usize = day
+                .strip_prefix("day")
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| anyhow!("unexpected table '{day}', expected 'dayNN'"))?;
+            let inputs = inputs
+                .into_iter()
+                .map(|(name, e)| {
+                    let expected = Expected {
+                        part1: e.part1.map(Answer::from),
+                        part2: e.part2.map(Answer::from),
+                        params: e.params,
+                    };
+                    (name, expected)
+                })
+                .collect();
+            days.insert(day, inputs);
+        }
+        Ok(Self { days })
+    }
+
+    pub fn expected(&self, day: usize, input_name: &str) -> Option<&Expected> {
+        self.days
+            .get(&day)
+            .and_then(|inputs| inputs.get(input_name))
+    }
+}
+
+// Where a year's inputs and their expected answers are kept, relative to the repository root.
+pub fn input_dir(year: u16) -> PathBuf {
+    Path::new("inputs").join(year.to_string())
+}
+
+pub fn answers_path(year: u16) -> PathBuf {
+    Path::new("answers").join(format!("{year}.toml"))
+}
+
+pub fn input_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+// Example inputs of a day in `dir`, like 'day09_example.txt' and 'day09_example2.txt', sorted
+// by name.
+pub fn example_inputs(dir: &Path, day: usize) -> Result<Vec<PathBuf>> {
+    let prefix = format!("day{day:02}_example");
+    let mut paths = vec![];
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("failed to read '{}'", dir.display()))?
+    {
+        let path = entry?.path();
+        if input_name(&path).starts_with(&prefix) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+// Every input in `dir` for batch runs, sorted by name. Hidden files and subdirectories are
+// skipped.
+pub fn batch_inputs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = vec![];
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("failed to read '{}'", dir.display()))?
+    {
+        let entry = entry?;
+        if entry.file_type()?.is_file() && !entry.file_name().to_string_lossy().starts_with('.') {
+            paths.push(entry.path());
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let m = Manifest::parse(
+            r#"
+            [day05.day05]
+            part1 = "CMZ"
+            part2 = "MCD"
+
+            [day10.day10_example]
+            part1 = 13140
+            params = { row = 10 }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            Some(&Expected {
+                part1: Some("CMZ".into()),
+                part2: Some("MCD".into()),
+                params: Params::new(),
+            }),
+            m.expected(5, "day05")
+        );
+        assert_eq!(
+            Some(&Expected {
+                part1: Some(13140.into()),
+                part2: None,
+                params: [("row".to_owned(), 10)].into(),
+            }),
+            m.expected(10, "day10_example")
+        );
+        assert_eq!(None, m.expected(10, "day10"));
+        assert!(Manifest::parse("[dayone.x]\npart1 = 1").is_err());
+    }
+
+    #[test]
+    fn check_test() {
+        let e = Expected {
+            part1: Some(1.into()),
+            part2: Some(2.into()),
+            params: Params::new(),
+        };
+        let answers = Answers {
+            part1: 1.into(),
+            part2: 3.into(),
+        };
+        assert_eq!(
+            vec![Mismatch {
+                part: 2,
+                expected: 2.into(),
+                actual: 3.into()
+            }],
+            e.check(&answers)
+        );
+    }
+
+    #[test]
+    fn input_name_test() {
+        assert_eq!("day01", input_name(Path::new("inputs/day01")));
+        assert_eq!(
+            "day09_example2",
+            input_name(Path::new("inputs/day09_example2.txt"))
+        );
+    }
+
+    #[test]
+    fn example_inputs_test() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(input_dir(2022));
+        assert_eq!(
+            vec![
+                dir.join("day09_example.txt"),
+                dir.join("day09_example2.txt")
+            ],
+            example_inputs(&dir, 9).unwrap()
+        );
+    }
+
+    #[test]
+    fn batch_inputs_test() {
+        let dir = std::env::temp_dir().join("aoc22-batch-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        for name in ["bob.txt", "alice", ".hidden"] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+        assert_eq!(
+            vec![dir.join("alice"), dir.join("bob.txt")],
+            batch_inputs(&dir).unwrap()
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(batch_inputs(&dir).is_err());
+    }
+}
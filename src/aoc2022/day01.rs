@@ -0,0 +1,40 @@
+use anyhow::{anyhow, Result};
+
+use crate::input::try_token_groups;
+use crate::solution::{Answer, Context, Solution};
+
+fn top3(calories: &[u32]) -> Result<Vec<u32>> {
+    let mut calories = calories.to_vec();
+    let l = calories.len();
+    let nth = l
+        .checked_sub(4)
+        .ok_or_else(|| anyhow!("not enough elements"))?;
+    let (_, _, top3) = calories.select_nth_unstable(nth);
+    Ok(top3.to_vec())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(try_token_groups::<u32>(input, "\n\n", None)?
+            .into_iter()
+            .map(|sub| sub.into_iter().sum())
+            .collect())
+    }
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let part1 = *top3(input)?
+            .iter()
+            .max()
+            .ok_or_else(|| anyhow!("not enough elements"))?;
+        Ok(part1.into())
+    }
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let part2: u32 = top3(input)?.iter().sum();
+        Ok(part2.into())
+    }
+}
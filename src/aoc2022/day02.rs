@@ -0,0 +1,120 @@
+use anyhow::{anyhow, Error, Result};
+use std::str::FromStr;
+
+const LOSE: char = 'X';
+const DRAW: char = 'Y';
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Piece {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+use Piece::*;
+
+use crate::input::try_tokens;
+use crate::solution::{Answer, Context, Solution};
+
+impl Piece {
+    fn play(&self, other: &Self) -> i32 {
+        if self == other {
+            return 3;
+        }
+        match (self, other) {
+            (Rock, Scissors) | (Scissors, Paper) | (Paper, Rock) => 6,
+            _ => 0,
+        }
+    }
+    fn value(&self) -> i32 {
+        match self {
+            Rock => 1,
+            Paper => 2,
+            Scissors => 3,
+        }
+    }
+    fn parse(c: char) -> Result<Self> {
+        match c {
+            'A' | 'X' => Ok(Rock),
+            'B' | 'Y' => Ok(Paper),
+            'C' | 'Z' => Ok(Scissors),
+            _ => Err(anyhow!("unknown piece '{c}'")),
+        }
+    }
+    fn select(&self, target: char) -> Piece {
+        if target == DRAW {
+            *self
+        } else if target == LOSE {
+            match self {
+                Rock => Scissors,
+                Scissors => Paper,
+                Paper => Rock,
+            }
+        } else {
+            match self {
+                Scissors => Rock,
+                Paper => Scissors,
+                Rock => Paper,
+            }
+        }
+    }
+}
+
+impl FromStr for Piece {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s.chars().next().ok_or_else(|| anyhow!("no chars"))?)
+    }
+}
+
+// A line of the input, like 'A Y'.
+struct Round(Piece, char);
+
+impl FromStr for Round {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (other, me) = s
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("expected two columns"))?;
+        let me: char = me.parse()?;
+        if !matches!(me, 'X' | 'Y' | 'Z') {
+            return Err(anyhow!("expected X, Y or Z, got '{me}'"));
+        }
+        Ok(Self(other.parse()?, me))
+    }
+}
+
+fn score((other, me): (Piece, Piece)) -> i32 {
+    me.value() + me.play(&other)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<(Piece, char)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(try_tokens(input, Some("\n"))?
+            .into_iter()
+            .map(|Round(other, me)| (other, me))
+            .collect())
+    }
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let part1 = input
+            .iter()
+            .map(|(a, b)| Ok(score((*a, Piece::parse(*b)?))))
+            .sum::<Result<i32>>()?;
+        Ok(part1.into())
+    }
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let part2: i32 = input
+            .iter()
+            .map(|(a, b)| (*a, a.select(*b)))
+            .map(score)
+            .sum();
+        Ok(part2.into())
+    }
+}
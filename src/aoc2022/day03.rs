@@ -0,0 +1,83 @@
+use crate::solution::{Answer, Context, Solution};
+use crate::{input::try_lines, U8Set};
+use anyhow::{anyhow, bail, Result};
+
+fn score(b: u8) -> u64 {
+    if b < b'a' {
+        let b = b - b'A' + 1;
+        b as u64 + 26
+    } else {
+        let b = b - b'a' + 1;
+        b as u64
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Vec<u8>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let rucksacks = try_lines(input, |l| {
+            match l.trim().bytes().find(|b| !b.is_ascii_alphabetic()) {
+                Some(b) => Err(anyhow!("unexpected '{}' in a rucksack", b as char)),
+                None => Ok(l.trim().bytes().collect()),
+            }
+        })?;
+        if rucksacks.len() % 3 != 0 {
+            bail!(
+                "expected groups of 3 elves, got {} rucksacks",
+                rucksacks.len()
+            );
+        }
+        Ok(rucksacks)
+    }
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let part1: u64 = input
+            .iter()
+            .flat_map(|v| {
+                let len = v.len() / 2;
+                let l: U8Set = v[..len].iter().collect();
+                let r: U8Set = v[len..].iter().collect();
+                l.intersection(&r).iter().next()
+            })
+            .map(score)
+            .sum();
+        Ok(part1.into())
+    }
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let part2: u64 = input
+            .chunks(3)
+            .flat_map(|c| {
+                let a: U8Set = c[0].iter().collect();
+                let b: U8Set = c[1].iter().collect();
+                let c: U8Set = c[2].iter().collect();
+                a.intersection(&b).intersection(&c).iter().next()
+            })
+            .map(score)
+            .sum();
+        Ok(part2.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_test() {
+        assert_eq!(score(b'a'), 1);
+        assert_eq!(score(b'z'), 26);
+        assert_eq!(score(b'A'), 27);
+        assert_eq!(score(b'Z'), 52);
+    }
+
+    #[test]
+    fn groups_test() {
+        assert!(Solver::parse("noop").is_err());
+        assert!(Solver::parse("ab\ncd\nef\nab\n").is_err());
+        assert_eq!(3, Solver::parse("ab\ncd\nef\n").unwrap().len());
+    }
+}
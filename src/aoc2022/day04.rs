@@ -0,0 +1,70 @@
+use anyhow::{anyhow, Error, Result};
+use std::str::FromStr;
+
+use crate::input::try_token_arrays;
+use crate::solution::{Answer, Context, Solution};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Range {
+    first: i64,
+    last: i64,
+}
+
+impl Range {
+    fn parse(s: &str) -> Result<Self> {
+        let (first, last) = s
+            .split_once('-')
+            .ok_or_else(|| anyhow!("expected a range like '2-4'"))?;
+        Ok(Self {
+            first: first.parse()?,
+            last: last.parse()?,
+        })
+    }
+
+    fn includes(&self, other: &Self) -> bool {
+        self.first >= other.first && self.last <= other.last
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        self.first >= other.first && self.first <= other.last
+    }
+}
+
+impl FromStr for Range {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+fn includes(a: &Range, b: &Range) -> bool {
+    a.includes(b) || b.includes(a)
+}
+
+fn overlaps(a: &Range, b: &Range) -> bool {
+    a.overlaps(b) || b.overlaps(a)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<(Range, Range)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(try_token_arrays::<Range, 2>(input, "\n", Some(","))?
+            .into_iter()
+            .map(|[a, b]| (a, b))
+            .collect())
+    }
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let part1 = input.iter().filter(|(a, b)| includes(a, b)).count();
+        Ok(part1.into())
+    }
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let part2 = input.iter().filter(|(a, b)| overlaps(a, b)).count();
+        Ok(part2.into())
+    }
+}
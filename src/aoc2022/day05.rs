@@ -0,0 +1,145 @@
+use anyhow::{anyhow, bail, Context as _, Error, Result};
+use std::str::FromStr;
+
+use crate::input::try_tokens_in;
+use crate::solution::{Answer, Context, Solution};
+
+#[derive(Debug)]
+struct Move {
+    from: usize,
+    to: usize,
+    count: usize,
+}
+
+impl FromStr for Move {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split(' ').collect::<Vec<_>>()[..] {
+            ["move", count, "from", from, "to", to] => Ok(Self {
+                from: from.parse()?,
+                to: to.parse()?,
+                count: count.parse()?,
+            }),
+            _ => Err(anyhow!("expected 'move N from A to B'")),
+        }
+    }
+}
+
+pub struct Input {
+    stacks: Vec<Vec<u8>>,
+    moves: Vec<Move>,
+}
+
+fn tops(stacks: &[Vec<u8>]) -> String {
+    // An emptied stack has no crate on top, shown as a space.
+    stacks
+        .iter()
+        .map(|s| s.last().map_or(' ', |&c| c as char))
+        .collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (stacks, moves) = input
+            .split_once("\n\n")
+            .ok_or_else(|| anyhow!("expected an empty line between stacks and moves"))?;
+        let stacks: Vec<Vec<u8>> = stacks.lines().map(|l| l.bytes().collect()).collect();
+        let stacks: Vec<Vec<u8>> = stacks
+            .last()
+            .ok_or_else(|| anyhow!("expected stacks before the moves"))?
+            .iter()
+            .enumerate()
+            .filter(|(_, column_name)| **column_name != b' ')
+            .map(|(column_idx, _)| {
+                stacks
+                    .iter()
+                    .filter_map(|l| l.get(column_idx))
+                    .copied()
+                    .filter(|c| c.is_ascii_alphabetic())
+                    .rev()
+                    .collect()
+            })
+            .collect();
+        let moves: Vec<Move> = try_tokens_in(input, moves, Some("\n")).context("in moves")?;
+        // Both parts move the same number of crates between the same stacks, so the heights
+        // are enough to tell whether every move has the crates it asks for.
+        let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+        for (i, m) in moves.iter().enumerate() {
+            if !(1..=stacks.len()).contains(&m.from) || !(1..=stacks.len()).contains(&m.to) {
+                bail!(
+                    "move {}: from {} to {} but there are {} stacks",
+                    i + 1,
+                    m.from,
+                    m.to,
+                    stacks.len()
+                );
+            }
+            if heights[m.from - 1] < m.count {
+                bail!(
+                    "move {}: {} crates from stack {} but it has {}",
+                    i + 1,
+                    m.count,
+                    m.from,
+                    heights[m.from - 1]
+                );
+            }
+            heights[m.from - 1] -= m.count;
+            heights[m.to - 1] += m.count;
+        }
+        Ok(Input { stacks, moves })
+    }
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let mut stacks = input.stacks.clone();
+        for m in &input.moves {
+            for _ in 0..m.count {
+                // `parse` checked that the stack has enough crates.
+                let tmp = stacks[m.from - 1].pop().unwrap();
+                stacks[m.to - 1].push(tmp);
+            }
+        }
+        Ok(tops(&stacks).into())
+    }
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let mut stacks = input.stacks.clone();
+        for m in &input.moves {
+            let cut_point = stacks[m.from - 1].len() - m.count;
+            let to_move = stacks[m.from - 1][cut_point..].to_vec();
+            stacks[m.from - 1].truncate(cut_point);
+            stacks[m.to - 1].extend_from_slice(&to_move);
+        }
+        Ok(tops(&stacks).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_test() {
+        let stacks = "[A]    \n[B] [C]\n 1   2 \n\n";
+        assert_eq!(
+            "in moves: line 6, column 1: invalid token 'move x': expected 'move N from A to B'",
+            format!(
+                "{:#}",
+                Solver::parse(&format!("{stacks}move 1 from 1 to 2\nmove x\n"))
+                    .err()
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            "move 2: 2 crates from stack 2 but it has 0",
+            Solver::parse(&format!("{stacks}move 1 from 2 to 1\nmove 2 from 2 to 1\n"))
+                .err()
+                .unwrap()
+                .to_string()
+        );
+    }
+}
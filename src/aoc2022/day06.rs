@@ -0,0 +1,39 @@
+use crate::solution::{Answer, Context, Solution};
+use crate::U8Set;
+use anyhow::{anyhow, Result};
+
+fn all_diff(v: &[u8]) -> bool {
+    let mut seen = U8Set::default();
+    for c in v {
+        if seen.insert(*c) {
+            return false;
+        }
+    }
+    true
+}
+
+fn find_marker(input: &[u8], len: usize) -> Result<usize> {
+    input
+        .windows(len)
+        .position(all_diff)
+        .map(|i| i + len)
+        .ok_or_else(|| anyhow!("no {len} different characters in a row"))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim().bytes().collect())
+    }
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        Ok(find_marker(input, 4)?.into())
+    }
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        Ok(find_marker(input, 14)?.into())
+    }
+}
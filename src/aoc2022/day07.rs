@@ -0,0 +1,135 @@
+use anyhow::{anyhow, bail, Result};
+use rustc_hash::FxHashMap as HashMap;
+use std::cmp::Reverse;
+
+use crate::solution::{Answer, Context, Solution};
+
+#[derive(Debug)]
+enum Entry<'a> {
+    File(&'a str, usize),
+    Dir(&'a str),
+}
+
+#[derive(Debug)]
+enum Cmd<'a> {
+    Ls(Vec<Entry<'a>>),
+    Cd(&'a str),
+}
+
+fn parse(input: &str) -> Result<Vec<Cmd<'_>>> {
+    let mut commands = vec![];
+    for (i, line) in input.lines().enumerate() {
+        let entry = match line.split(' ').collect::<Vec<_>>()[..] {
+            ["$", "ls"] => {
+                commands.push(Cmd::Ls(vec![]));
+                continue;
+            }
+            ["$", "cd", dir] => {
+                commands.push(Cmd::Cd(dir));
+                continue;
+            }
+            ["dir", name] => Entry::Dir(name),
+            [size, name] => Entry::File(
+                name,
+                size.parse()
+                    .map_err(|e| anyhow!("line {}: invalid size '{size}': {e}", i + 1))?,
+            ),
+            [""] => continue,
+            _ => bail!("line {}: unexpected '{line}'", i + 1),
+        };
+        match commands.last_mut() {
+            Some(Cmd::Ls(entries)) => entries.push(entry),
+            _ => bail!("line {}: '{line}' isn't the output of ls", i + 1),
+        }
+    }
+    Ok(commands)
+}
+
+fn reconstruct_fs(commands: Vec<Cmd>) -> Vec<(String, Vec<Entry>)> {
+    let mut file_system: HashMap<Vec<String>, Vec<Entry>> = HashMap::default();
+    let mut cwd = vec![];
+    for c in commands {
+        match c {
+            Cmd::Cd(s) if s == "/" => {
+                cwd = vec![s.to_owned()];
+            }
+            Cmd::Cd(s) if s == ".." => {
+                cwd.pop();
+            }
+            Cmd::Cd(s) => {
+                cwd.push(s.to_owned());
+            }
+            Cmd::Ls(entries) => {
+                file_system.entry(cwd.clone()).or_default().extend(entries);
+            }
+        }
+    }
+    let mut file_system: Vec<(String, Vec<Entry>)> = file_system
+        .into_iter()
+        .map(|(name, content)| (name.join("/"), content))
+        .collect();
+
+    file_system.sort_unstable_by_key(|(name, _)| Reverse(name.len()));
+    file_system
+}
+
+fn compute_total_sizes(file_system: Vec<(String, Vec<Entry>)>) -> HashMap<String, usize> {
+    let mut total_sizes: HashMap<String, usize> = HashMap::default();
+    for (name, entries) in file_system {
+        let mut total = 0;
+        for e in entries {
+            match e {
+                Entry::File(_, size) => total += size,
+                Entry::Dir(d) => {
+                    let name = format!("{name}/{d}");
+                    total += total_sizes.get(&name).unwrap_or(&0);
+                }
+            }
+        }
+        total_sizes.insert(name, total);
+    }
+    total_sizes
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = HashMap<String, usize>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let commands_and_outputs = parse(input)?;
+        let file_system = reconstruct_fs(commands_and_outputs);
+        let total_sizes = compute_total_sizes(file_system);
+        if !total_sizes.contains_key("/") {
+            bail!("the root directory is never listed");
+        }
+        Ok(total_sizes)
+    }
+
+    fn part1(total_sizes: &Self::Input, _: &Context) -> Result<Answer> {
+        let part1: usize = total_sizes
+            .iter()
+            .map(|(_, size)| size)
+            .filter(|s| **s <= 100000)
+            .sum();
+        Ok(part1.into())
+    }
+
+    fn part2(total_sizes: &Self::Input, _: &Context) -> Result<Answer> {
+        let total = 70000000;
+        let need = 30000000;
+        let current_unused = total - total_sizes["/"];
+        let to_delete = need - current_unused;
+
+        let mut total_sizes: Vec<(&String, usize)> = total_sizes
+            .iter()
+            .map(|(name, size)| (name, *size))
+            .collect();
+        total_sizes.sort_unstable_by_key(|(_, size)| *size);
+        let idx = match total_sizes.binary_search_by_key(&to_delete, |(_, size)| *size) {
+            Ok(i) => i,
+            Err(i) => i,
+        };
+        Ok(total_sizes[idx].1.into())
+    }
+}
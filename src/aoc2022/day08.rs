@@ -0,0 +1,86 @@
+use anyhow::{anyhow, bail, Result};
+use itertools::{
+    FoldWhile::{Continue, Done},
+    Itertools,
+};
+use std::iter::once;
+
+use crate::geom::{Dir4, Point2};
+use crate::grid::{Grid, Pos};
+use crate::solution::{Answer, Context, Solution};
+
+fn scenic_score(trees: &Grid<i8>, pos: Pos) -> usize {
+    let start_h = trees[pos];
+    Dir4::ALL.into_iter().fold(1, |score, dir| {
+        score
+            * trees
+                .ray(pos, dir.offset())
+                .fold_while(0, |acc, p| {
+                    if trees[p] >= start_h {
+                        Done(acc + 1)
+                    } else {
+                        Continue(acc + 1)
+                    }
+                })
+                .into_inner()
+    })
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Grid<i8>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let trees = Grid::parse(input, |c| {
+            c.to_digit(10)
+                .map(|d| d as i8)
+                .ok_or_else(|| anyhow!("expected a digit, got '{c}'"))
+        })?;
+        if trees.width() == 0 {
+            bail!("expected a non-empty rectangle of trees");
+        }
+        Ok(trees)
+    }
+
+    fn part1(trees: &Self::Input, _: &Context) -> Result<Answer> {
+        let mut visible = trees.map(|_| false);
+        let (width, height) = (trees.width(), trees.height());
+
+        // Look into the forest from every tree on the edge, away from that edge.
+        let lines_of_sight = (0..height)
+            .flat_map(|y| {
+                [
+                    (Point2::new(0, y), Dir4::Right),
+                    (Point2::new(width - 1, y), Dir4::Left),
+                ]
+            })
+            .chain((0..width).flat_map(|x| {
+                [
+                    (Point2::new(x, 0), Dir4::Down),
+                    (Point2::new(x, height - 1), Dir4::Up),
+                ]
+            }));
+        for (start, dir) in lines_of_sight {
+            let mut last = -1i8;
+            for p in once(start).chain(trees.ray(start, dir.offset())) {
+                if trees[p] > last {
+                    visible[p] = true;
+                    last = trees[p];
+                }
+            }
+        }
+
+        let part1 = visible.iter().filter(|(_, b)| **b).count();
+        Ok(part1.into())
+    }
+
+    fn part2(trees: &Self::Input, _: &Context) -> Result<Answer> {
+        let part2 = trees
+            .positions()
+            .map(|pos| scenic_score(trees, pos))
+            .max()
+            .unwrap();
+        Ok(part2.into())
+    }
+}
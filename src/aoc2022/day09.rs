@@ -0,0 +1,80 @@
+use anyhow::{anyhow, Error, Result};
+use rustc_hash::FxHashSet as HashSet;
+use std::str::FromStr;
+
+use crate::geom::{Dir4, Point2};
+use crate::input::try_tokens;
+use crate::solution::{Answer, Context, Solution};
+
+type Pos = Point2<i16>;
+
+fn move_to(knot: Pos, target: Pos) -> Pos {
+    if knot.chebyshev(target) <= 1 {
+        return knot;
+    }
+    let diff = target - knot;
+    knot + Pos::new(diff.x.signum(), diff.y.signum())
+}
+
+fn parse_dir(s: &str) -> Result<Dir4> {
+    match s {
+        "R" => Ok(Dir4::Right),
+        "U" => Ok(Dir4::Up),
+        "L" => Ok(Dir4::Left),
+        "D" => Ok(Dir4::Down),
+        s => Err(anyhow!("unexpected string: '{s}'")),
+    }
+}
+
+// A line of the input, like 'R 4'.
+struct Motion(Dir4, usize);
+
+impl FromStr for Motion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (dir, count) = s
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("expected direction and count"))?;
+        Ok(Self(parse_dir(dir)?, count.parse()?))
+    }
+}
+
+fn simulate_and_find_tail_positions(mut rope: Vec<Pos>, moves: &[(Dir4, usize)]) -> HashSet<Pos> {
+    let mut seen = HashSet::default();
+    seen.insert(*rope.last().unwrap());
+
+    for (dir, count) in moves {
+        for _ in 0..*count {
+            rope[0] += dir.offset();
+            for i in 1..rope.len() {
+                rope[i] = move_to(rope[i], rope[i - 1]);
+            }
+            seen.insert(*rope.last().unwrap());
+        }
+    }
+    seen
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<(Dir4, usize)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(try_tokens(input, Some("\n"))?
+            .into_iter()
+            .map(|Motion(dir, count)| (dir, count))
+            .collect())
+    }
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let rope = vec![Pos { x: 0, y: 0 }; 2];
+        Ok(simulate_and_find_tail_positions(rope, input).len().into())
+    }
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let rope = vec![Pos { x: 0, y: 0 }; 10];
+        Ok(simulate_and_find_tail_positions(rope, input).len().into())
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::{bail, Result};
+use std::str::FromStr;
+
+use crate::input::try_tokens;
+use crate::solution::{Answer, Context, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Noop,
+    Addx(i32),
+}
+
+impl Op {
+    fn len(&self) -> usize {
+        match self {
+            Self::Addx(_) => 2,
+            Self::Noop => 1,
+        }
+    }
+}
+
+impl FromStr for Op {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.split(' ').collect::<Vec<_>>()[..] {
+            ["addx", v] => Ok(Self::Addx(v.parse()?)),
+            ["noop"] => Ok(Self::Noop),
+            _ => Err(anyhow::anyhow!("unexpected '{s}'")),
+        }
+    }
+}
+
+fn render(screen: &[char]) -> String {
+    screen
+        .chunks(40)
+        .map(|line| line.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn execute(ops: &[Op]) -> Vec<i32> {
+    let mut ops = ops.to_vec();
+    let mut current_op_cyc = 0;
+    let mut current_op: Option<Op> = None;
+    let mut x = 1;
+    let mut xs = vec![];
+    ops.reverse();
+    while !ops.is_empty() || current_op.is_some() {
+        match current_op {
+            None => {
+                current_op = ops.pop();
+                current_op_cyc = 1;
+            }
+
+            Some(op) if op.len() == current_op_cyc => {
+                if let Some(Op::Addx(n)) = current_op {
+                    x += n;
+                }
+                current_op = ops.pop();
+                current_op_cyc = 1;
+            }
+            _ => {
+                current_op_cyc += 1;
+            }
+        }
+        xs.push(x);
+    }
+    xs
+}
+
+// Register value during each cycle, failing if the program ends before `cycles` of them.
+fn trace(ops: &[Op], cycles: usize) -> Result<Vec<i32>> {
+    let xs = execute(ops);
+    if xs.len() < cycles {
+        bail!(
+            "the program ends after {} cycles, expected at least {cycles}",
+            xs.len()
+        );
+    }
+    Ok(xs)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Op>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        try_tokens(input, Some("\n"))
+    }
+
+    fn part1(ops: &Self::Input, _: &Context) -> Result<Answer> {
+        let xs = trace(ops, 220)?;
+        let idxs = [20i32, 60, 100, 140, 180, 220];
+        let part1 = idxs
+            .into_iter()
+            .map(|i| xs[(i - 1) as usize] * i)
+            .sum::<i32>();
+        Ok(part1.into())
+    }
+
+    fn part2(ops: &Self::Input, _: &Context) -> Result<Answer> {
+        let xs = trace(ops, 240)?;
+        let mut screen = vec!['X'; 240];
+
+        for c in 0i32..240 {
+            let cc = c % 40;
+            screen[c as usize] = if (xs[c as usize] - cc).abs() <= 1 {
+                '#'
+            } else {
+                ' '
+            };
+        }
+
+        Ok(Answer::Multiline(render(&screen)))
+    }
+}
@@ -1,8 +1,7 @@
-use anyhow::{anyhow, Error, Result};
+use anyhow::{anyhow, bail, Error, Result};
 use std::str::FromStr;
-use std::time::{Duration, Instant};
 
-use crate::input::tokens;
+use crate::solution::{Answer, Context, Solution};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct Test {
@@ -20,30 +19,23 @@ impl Test {
         }
     }
 
-    fn parse(lines: &[&str]) -> Self {
-        let divisible_by: u64 = lines[0]
-            .strip_prefix("  Test: divisible by ")
-            .unwrap()
-            .parse()
-            .unwrap();
-        let if_true: usize = lines[1]
-            .strip_prefix("    If true: throw to monkey ")
-            .unwrap()
-            .parse()
-            .unwrap();
-        let if_false: usize = lines[2]
-            .strip_prefix("    If false: throw to monkey ")
-            .unwrap()
-            .parse()
-            .unwrap();
-        Self {
-            divisible_by,
-            if_true,
-            if_false,
-        }
+    fn parse(lines: &[&str]) -> Result<Self> {
+        Ok(Self {
+            divisible_by: field(lines, 3, "  Test: divisible by ")?.parse()?,
+            if_true: field(lines, 4, "    If true: throw to monkey ")?.parse()?,
+            if_false: field(lines, 5, "    If false: throw to monkey ")?.parse()?,
+        })
     }
 }
 
+// The rest of line `i` of a monkey's description, after `prefix`.
+fn field<'a>(lines: &[&'a str], i: usize, prefix: &str) -> Result<&'a str> {
+    lines
+        .get(i)
+        .and_then(|l| l.strip_prefix(prefix))
+        .ok_or_else(|| anyhow!("line {}: expected '{}'", i + 1, prefix.trim()))
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 enum Num {
     N(u64),
@@ -65,7 +57,7 @@ impl FromStr for Num {
         if s == "old" {
             Ok(Num::Old)
         } else {
-            Ok(Num::N(s.parse().unwrap()))
+            Ok(Num::N(s.parse()?))
         }
     }
 }
@@ -88,18 +80,16 @@ impl Operation {
 impl FromStr for Operation {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self> {
-        let s = s.strip_prefix("  Operation: new = old ").unwrap();
-        let mut s = s.split(' ');
-        match s.next().unwrap() {
-            "*" => Ok(Operation::Times(s.next().unwrap().parse().unwrap())),
-            "+" => Ok(Operation::Add(s.next().unwrap().parse().unwrap())),
-            s => Err(anyhow!("unexpected '{s}'")),
+        match s.split(' ').collect::<Vec<_>>()[..] {
+            ["*", n] => Ok(Operation::Times(n.parse()?)),
+            ["+", n] => Ok(Operation::Add(n.parse()?)),
+            _ => Err(anyhow!("unexpected '{s}'")),
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-struct Monkey {
+pub struct Monkey {
     items: Vec<u64>,
     operation: Operation,
     test: Test,
@@ -107,31 +97,28 @@ struct Monkey {
 }
 
 impl Monkey {
-    fn parse(lines: &[&str]) -> Self {
-        let starting = lines[1];
-        let items: Vec<u64> = starting
-            .strip_prefix("  Starting items: ")
-            .unwrap()
+    fn parse(lines: &[&str]) -> Result<Self> {
+        let items = field(lines, 1, "  Starting items: ")?
             .split(", ")
-            .map(|n| n.parse().unwrap())
-            .collect();
-        let operation = lines[2].parse().unwrap();
-        let test = Test::parse(&lines[3..]);
-        Self {
+            .map(|n| n.parse())
+            .collect::<Result<_, _>>()?;
+        let operation = field(lines, 2, "  Operation: new = old ")?.parse()?;
+        let test = Test::parse(lines)?;
+        Ok(Self {
             items,
             operation,
             test,
             inspect_count: 0,
-        }
+        })
     }
 }
 
 impl FromStr for Monkey {
-    type Err = ();
+    type Err = Error;
 
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let s: Vec<&str> = s.split("\n").collect();
-        Ok(Self::parse(&s))
+    fn from_str(s: &str) -> Result<Self> {
+        let s: Vec<&str> = s.lines().collect();
+        Self::parse(&s)
     }
 }
 
@@ -161,23 +148,34 @@ fn simulate(mut monkeys: Vec<Monkey>, part: usize) -> usize {
     inspect_counts.into_iter().take(2).product()
 }
 
-pub fn solve(input: &str, verify_expected: bool, output: bool) -> Result<Duration> {
-    let monkeys: Vec<Monkey> = tokens(input, Some("\n\n"));
-
-    let s = Instant::now();
-
-    let part1 = simulate(monkeys.clone(), 1);
-    let part2 = simulate(monkeys, 2);
-
-    let e = s.elapsed();
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let monkeys: Vec<Monkey> = input
+            .split("\n\n")
+            .filter(|m| !m.trim().is_empty())
+            .enumerate()
+            .map(|(i, m)| m.parse().map_err(|e| anyhow!("monkey {i}: {e:#}")))
+            .collect::<Result<_>>()?;
+        for (i, m) in monkeys.iter().enumerate() {
+            if m.test.divisible_by == 0 {
+                bail!("monkey {i}: can't test divisibility by 0");
+            }
+            if m.test.if_true.max(m.test.if_false) >= monkeys.len() {
+                bail!("monkey {i}: throws to a monkey that doesn't exist");
+            }
+        }
+        Ok(monkeys)
+    }
 
-    if verify_expected {
-        assert_eq!(151312, part1);
-        assert_eq!(51382025916, part2);
+    fn part1(monkeys: &Self::Input, _: &Context) -> Result<Answer> {
+        Ok(simulate(monkeys.clone(), 1).into())
     }
-    if output {
-        println!("\t{}", part1);
-        println!("\t{}", part2);
+
+    fn part2(monkeys: &Self::Input, _: &Context) -> Result<Answer> {
+        Ok(simulate(monkeys.clone(), 2).into())
     }
-    Ok(e)
 }
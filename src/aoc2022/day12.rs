@@ -0,0 +1,93 @@
+use anyhow::{anyhow, Result};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::dijkstra::{astar, bfs_layers};
+use crate::grid::{Grid, Pos};
+use crate::solution::{Answer, Context, Solution};
+
+fn can_move(from: u8, to: u8) -> bool {
+    let from = from as i8;
+    let to = to as i8;
+    (to - from) <= 1
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct State {
+    path_len: u32,
+    height: u8,
+    next: Pos,
+}
+
+pub struct Input {
+    m: Grid<u8>,
+    start: Pos,
+    target: Pos,
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut m = Grid::parse(input, |c| match c {
+            'a'..='z' | 'S' | 'E' => Ok(c as u8),
+            _ => Err(anyhow!("unexpected '{c}'")),
+        })?;
+
+        let start = m
+            .position(|b| *b == b'S')
+            .ok_or_else(|| anyhow!("no start 'S'"))?;
+        let target = m
+            .position(|b| *b == b'E')
+            .ok_or_else(|| anyhow!("no target 'E'"))?;
+
+        m[start] = b'a';
+        m[target] = b'z';
+
+        Ok(Input { m, start, target })
+    }
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let m = &input.m;
+        let target = input.target;
+        let neighbours_of = |p: &Pos| {
+            m.neighbours4(*p)
+                .filter(|next| can_move(m[*p], m[*next]))
+                .map(|next| (next, 1))
+                .collect()
+        };
+        // Every step goes to an adjacent square, so no path is shorter than this.
+        let distance = |p: &Pos| p.manhattan(target) as u64;
+        let (part1, _) = astar(input.start, |p| *p == target, neighbours_of, distance)
+            .ok_or_else(|| anyhow!("can't reach the target from the start"))?;
+        Ok(part1.into())
+    }
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let m = &input.m;
+        let starts = m.iter().filter(|(_, h)| **h == b'a').map(|(p, _)| p);
+        let neighbours_of = |p: &Pos| {
+            let p = *p;
+            m.neighbours4(p)
+                .filter(move |next| can_move(m[p], m[*next]))
+        };
+        let part2 = bfs_layers(starts, neighbours_of)
+            .position(|layer| layer.contains(&input.target))
+            .ok_or_else(|| anyhow!("can't reach the target from any 'a'"))?;
+        Ok(part2.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_move_test() {
+        assert!(can_move(b'z', b'a'));
+        assert!(can_move(b'm', b'n'));
+        assert!(!can_move(b'm', b'o'));
+    }
+}
@@ -1,29 +1,29 @@
-use anyhow::Result;
+use anyhow::{anyhow, Error, Result};
 use nom::bytes::complete::tag;
 use nom::character::complete::{char, u8};
 use nom::{branch::alt, multi::separated_list0, sequence::delimited, IResult};
 use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::str::FromStr;
-use std::time::{Duration, Instant};
 
 #[derive(Debug, PartialEq, Clone, Eq)]
-enum Packet {
+pub enum Packet {
     Num(u8),
     List(Vec<Packet>),
 }
 
 impl FromStr for Packet {
-    type Err = ();
+    type Err = Error;
 
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        Ok(parse(s))
+    fn from_str(s: &str) -> Result<Self> {
+        parse(s)
     }
 }
 
 use Packet::*;
 
-use crate::input::token_groups;
+use crate::input::try_token_arrays;
+use crate::solution::{Answer, Context, Solution};
 
 fn packet(i: &str) -> IResult<&str, Packet> {
     fn num(input: &str) -> IResult<&str, Packet> {
@@ -78,53 +78,53 @@ impl PartialOrd for Packet {
     }
 }
 
-fn parse(s: &str) -> Packet {
-    let (rest, packet) = packet(s).unwrap();
-    debug_assert!(rest.is_empty());
-    packet
+fn parse(s: &str) -> Result<Packet> {
+    match packet(s) {
+        Ok(("", packet)) => Ok(packet),
+        Ok((rest, _)) => Err(anyhow!("unexpected '{rest}' after the packet")),
+        Err(e) => Err(anyhow!("invalid packet: {e}")),
+    }
 }
 
-pub fn solve(input: &str, verify_expected: bool, output: bool) -> Result<Duration> {
-    let input: Vec<(Packet, Packet)> = token_groups(input, "\n\n", None)
-        .into_iter()
-        .map(|v: Vec<Packet>| (v[0].clone(), v[1].clone()))
-        .collect();
-
-    let s = Instant::now();
-
-    let part1: usize = input
-        .iter()
-        .enumerate()
-        .filter(|(_, (l, r))| Ordering::Less == l.cmp(r))
-        .map(|(i, _)| i + 1)
-        .sum();
-
-    let mut input: Vec<_> = input.into_iter().flat_map(|(l, r)| [l, r]).collect();
-
-    let d1 = parse("[[2]]");
-    let d2 = parse("[[6]]");
-    input.push(d1.clone());
-    input.push(d2.clone());
-    input.sort_unstable();
-
-    let part2: usize = input
-        .into_iter()
-        .enumerate()
-        .filter(|(_, p)| p == &d1 || p == &d2)
-        .map(|(i, _)| i + 1)
-        .product();
-
-    let e = s.elapsed();
-
-    if verify_expected {
-        assert_eq!(5720, part1);
-        assert_eq!(23504, part2);
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<(Packet, Packet)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(try_token_arrays(input, "\n\n", None)?
+            .into_iter()
+            .map(|[a, b]: [Packet; 2]| (a, b))
+            .collect())
     }
-    if output {
-        println!("\t{}", part1);
-        println!("\t{}", part2);
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let part1: usize = input
+            .iter()
+            .enumerate()
+            .filter(|(_, (l, r))| Ordering::Less == l.cmp(r))
+            .map(|(i, _)| i + 1)
+            .sum();
+        Ok(part1.into())
+    }
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let mut input: Vec<_> = input.iter().flat_map(|(l, r)| [l, r]).cloned().collect();
+
+        let d1 = parse("[[2]]")?;
+        let d2 = parse("[[6]]")?;
+        input.push(d1.clone());
+        input.push(d2.clone());
+        input.sort_unstable();
+
+        let part2: usize = input
+            .into_iter()
+            .enumerate()
+            .filter(|(_, p)| p == &d1 || p == &d2)
+            .map(|(i, _)| i + 1)
+            .product();
+        Ok(part2.into())
     }
-    Ok(e)
 }
 
 #[cfg(test)]
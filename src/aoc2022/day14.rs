@@ -0,0 +1,125 @@
+use anyhow::{anyhow, bail, Result};
+use itertools::iterate;
+use rustc_hash::FxHashSet as HashSet;
+
+use crate::geom::Point2;
+use crate::grid::Grid;
+use crate::input::try_lines;
+use crate::solution::{Answer, Context, Solution};
+
+type Pos = Point2<i32>;
+
+const MOVE_DIRS: [Pos; 3] = [Pos::new(0, 1), Pos::new(-1, 1), Pos::new(1, 1)];
+
+fn parse_pos(s: &str) -> Result<Pos> {
+    let (x, y) = s
+        .split_once(',')
+        .ok_or_else(|| anyhow!("expected a point like '498,4', got '{s}'"))?;
+    Ok(Pos::new(x.parse()?, y.parse()?))
+}
+
+// Cell of the cave.
+fn cell(p: Pos) -> Point2<usize> {
+    Point2::new(p.x as usize, p.y as usize)
+}
+
+fn next(sand: Pos, cave: &Grid<State>, floor_y: Option<i32>) -> Option<Pos> {
+    if let Some(y) = floor_y {
+        if (sand.y + 1) == y {
+            return None;
+        }
+    }
+    MOVE_DIRS
+        .into_iter()
+        .map(|dir| sand + dir)
+        .find(|p| cave[cell(*p)] == State::Empty)
+}
+
+fn parse(s: &str) -> Result<Vec<Pos>> {
+    s.split(" -> ").map(parse_pos).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Stone,
+    Sand,
+    Empty,
+}
+
+fn simulate(rocks: &HashSet<Pos>, floor_y: Option<i32>) -> usize {
+    let start = Pos::new(500, 0);
+    let max_y = rocks.iter().map(|p| p.y).max().unwrap();
+    assert!(max_y < 500);
+    let width = 500 + max_y + 3;
+    let mut cave = Grid::new(width as usize, (max_y + 3) as usize, State::Empty);
+    for pos in rocks {
+        cave[cell(*pos)] = State::Stone;
+    }
+    let mut last_path_cache = vec![start];
+    for i in 0.. {
+        let mut sand = start;
+        // Sand will travel the same way almost till the end
+        while let Some(pos) = last_path_cache.pop() {
+            if cave[cell(pos)] == State::Empty {
+                sand = pos;
+                break;
+            }
+        }
+        last_path_cache.push(sand);
+        while let Some(p) = next(sand, &cave, floor_y) {
+            sand = p;
+            last_path_cache.push(sand);
+            if floor_y.is_none() && sand.y > max_y {
+                return i;
+            }
+        }
+        cave[cell(sand)] = State::Sand;
+        if floor_y.is_some() && sand == start {
+            return i + 1;
+        }
+    }
+    unreachable!()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = HashSet<Pos>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let rocks: HashSet<Pos> = try_lines(input, parse)?
+            .into_iter()
+            .flat_map(|path| {
+                path.windows(2)
+                    .flat_map(|segment| {
+                        let (from, to) = (segment[0], segment[1]);
+                        let d = to - from;
+                        let dir = Pos::new(d.x.signum(), d.y.signum());
+                        iterate(from, move |p| *p + dir).take(from.chebyshev(to) as usize + 1)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let max_y = rocks
+            .iter()
+            .map(|p| p.y)
+            .max()
+            .ok_or_else(|| anyhow!("no rocks"))?;
+        if let Some(p) = rocks
+            .iter()
+            .find(|p| p.y < 0 || max_y >= 500 || p.x <= 0 || p.x >= 500 + max_y + 2)
+        {
+            bail!("rock at {},{} is outside the cave", p.x, p.y);
+        }
+        Ok(rocks)
+    }
+
+    fn part1(cave: &Self::Input, _: &Context) -> Result<Answer> {
+        Ok(simulate(cave, None).into())
+    }
+
+    fn part2(cave: &Self::Input, _: &Context) -> Result<Answer> {
+        let max_y = cave.iter().map(|p| p.y).max().unwrap();
+        Ok(simulate(cave, Some(max_y + 2)).into())
+    }
+}
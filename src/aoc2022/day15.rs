@@ -0,0 +1,134 @@
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+use smallvec::{smallvec, SmallVec};
+use std::collections::HashSet;
+
+use crate::geom::Point2;
+use crate::input::try_lines;
+use crate::solution::{Answer, Context, Solution};
+
+type V<T> = SmallVec<[T; 8]>;
+// Sensor, beacon, top and bottom row of the covered area and sensor to beacon distance.
+type Sensor = (Pos, Pos, i32, i32, i32);
+
+type Pos = Point2<i32>;
+
+fn sorted_overlap((_, max1): (i32, i32), (min2, _): (i32, i32)) -> bool {
+    min2 <= max1 || max1 + 1 == min2
+}
+
+fn parse(s: &str) -> Result<(Pos, Pos)> {
+    let words = s.split(' ').collect_vec();
+    if words.len() != 10 {
+        bail!("expected 10 words, got {}", words.len());
+    }
+    // The number in word `i`, between `prefix` and `suffix`.
+    let coord = |i: usize, prefix: &str, suffix: &str| -> Result<i32> {
+        words[i]
+            .strip_prefix(prefix)
+            .and_then(|w| w.strip_suffix(suffix))
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| anyhow!("expected '{prefix}N{suffix}' at word {i}"))
+    };
+    let s = Pos::new(coord(2, "x=", ",")?, coord(3, "y=", ":")?);
+    let b = Pos::new(coord(8, "x=", ",")?, coord(9, "y=", "")?);
+    Ok((s, b))
+}
+
+fn min_max_in_range_for(s: Pos, b: Pos, dist: i32, row: i32) -> Option<(i32, i32)> {
+    debug_assert_eq!(dist, s.manhattan(b));
+    let off = dist - (s.y - row).abs();
+    if off < 0 {
+        None
+    } else {
+        Some((s.x - off, s.x + off))
+    }
+}
+
+fn top_and_bot(s: Pos, b: Pos) -> (i32, i32) {
+    let d = s.manhattan(b);
+    let top_row = s.y - d;
+    let bot_row = s.y + d;
+    (top_row, bot_row)
+}
+
+fn intervals_for_row(row: i32, input: &[Sensor]) -> V<(i32, i32)> {
+    let mut intervals: V<(i32, i32)> = input
+        .iter()
+        .filter(|(_, _, top, bot, _)| *bot >= row && *top <= row)
+        .flat_map(|(s, b, _, _, dist)| min_max_in_range_for(*s, *b, *dist, row))
+        .collect();
+    intervals.sort_unstable_by_key(|(min, _)| *min);
+
+    let Some((first, rest)) = intervals.split_first() else {
+        return smallvec![];
+    };
+    let mut intervals_merged: V<(i32, i32)> = smallvec![*first];
+    for next in rest {
+        let last_idx = intervals_merged.len() - 1;
+        let last = intervals_merged[last_idx];
+        if sorted_overlap(last, *next) {
+            if next.1 > last.1 {
+                intervals_merged[last_idx].1 = next.1;
+            }
+        } else {
+            intervals_merged.push(*next);
+        }
+    }
+    intervals_merged
+}
+
+fn find_freq(input: &[Sensor], max_row: i32) -> Result<i64> {
+    // rayon bridge_par + find_any can speed this up from 120ms to 90ms
+    (0..=max_row)
+        .rev()
+        .map(|row| (row, intervals_for_row(row, input)))
+        .find(|(_, int)| int.len() > 1)
+        .map(|(row, int)| (int[0].1 as i64 + 1) * 4000000 + row as i64)
+        .ok_or_else(|| anyhow!("no room for the distress beacon up to {max_row}"))
+}
+
+// Row to count positions without a beacon in and the largest coordinate of the distress
+// beacon. The puzzle gives 2000000 and 4000000, its example sets its own in answers/2022.toml.
+fn row_and_max(ctx: &Context) -> Result<(i32, i32)> {
+    let param = |name, default| -> Result<i32> {
+        let value = ctx.param(name).unwrap_or(default);
+        i32::try_from(value).map_err(|_| anyhow!("{name} {value} is out of range"))
+    };
+    Ok((param("row", 2000000)?, param("max", 4000000)?))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Sensor>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut input: Vec<_> = try_lines(input, parse)?
+            .into_iter()
+            .map(|(s, b)| {
+                let (top, bot) = top_and_bot(s, b);
+                let dist = s.manhattan(b);
+                (s, b, top, bot, dist)
+            })
+            .collect();
+        input.sort_by_key(|(_, _, top, _, _)| *top);
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input, ctx: &Context) -> Result<Answer> {
+        let (row, _) = row_and_max(ctx)?;
+        let b_in_row: HashSet<_> = input.iter().map(|v| v.1).filter(|v| v.y == row).collect();
+        let part1 = intervals_for_row(row, input)
+            .iter()
+            .map(|(min, max)| max - min + 1)
+            .sum::<i32>() as usize
+            - b_in_row.len();
+        Ok(part1.into())
+    }
+
+    fn part2(input: &Self::Input, ctx: &Context) -> Result<Answer> {
+        let (_, max) = row_and_max(ctx)?;
+        Ok(find_freq(input, max)?.into())
+    }
+}
@@ -0,0 +1,296 @@
+use crate::dijkstra::{all_pairs_bfs, Compressed};
+use crate::input::try_lines;
+use crate::progress::Progress;
+use crate::solution::{Answer, Context, Solution};
+use crate::U8Set;
+use anyhow::{anyhow, bail, Result};
+use itertools::{iproduct, Itertools};
+use rustc_hash::FxHashMap as HashMap;
+use smallvec::SmallVec;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+
+type G = Vec<(i8, V<u8>)>;
+type V<T> = SmallVec<[T; 14]>;
+
+// A line like 'Valve BB has flow rate=13; tunnels lead to valves CC, AA'.
+fn parse(s: &str) -> Result<(String, (i8, V<String>))> {
+    let words = s.split(' ').collect_vec();
+    let [_, from, _, _, rate, _, _, _, _, to @ ..] = &words[..] else {
+        bail!("expected a valve, its flow rate and the valves it leads to");
+    };
+    let rate = rate
+        .strip_prefix("rate=")
+        .and_then(|r| r.strip_suffix(';'))
+        .ok_or_else(|| anyhow!("expected 'rate=N;' at word 4"))?;
+    let rate: i8 = rate
+        .parse()
+        .map_err(|e| anyhow!("invalid flow rate '{rate}': {e}"))?;
+    let to = to
+        .iter()
+        .map(|s| s.strip_suffix(',').unwrap_or(s).to_owned())
+        .collect();
+    Ok((from.to_string(), (rate, to)))
+}
+
+// The valves worth opening and the start, with the minutes it takes to walk between them.
+struct Valves {
+    rates: Vec<usize>,
+    dist: Compressed,
+    start: u8,
+}
+
+impl Valves {
+    fn new(g: &G, start_node: u8) -> Result<Self> {
+        let adjacency: Vec<Vec<usize>> = g
+            .iter()
+            .map(|(_, out)| out.iter().map(|n| *n as usize).collect())
+            .collect();
+        let dist = all_pairs_bfs(&adjacency).compress(|v| g[v].0 != 0 || v == start_node as usize);
+        if dist.nodes.len() > 50 {
+            bail!(
+                "expected at most 50 valves worth opening, got {}",
+                dist.nodes.len()
+            );
+        }
+        let rates = dist.nodes.iter().map(|v| g[*v].0 as usize).collect();
+        let start = dist.nodes.iter().position(|v| *v == start_node as usize);
+        Ok(Self {
+            rates,
+            start: start.ok_or_else(|| anyhow!("valve AA was compressed away"))? as u8,
+            dist,
+        })
+    }
+
+    fn worth_opening(&self) -> U8Set {
+        let mut valves: U8Set = (0..self.rates.len())
+            .filter(|v| self.rates[*v] != 0)
+            .map(|v| v as u8)
+            .collect();
+        valves.max = 50;
+        valves
+    }
+
+    // Walks from the current valve to `target` and opens it, if there's time left to do so.
+    fn open(
+        &self,
+        world: &World,
+        score: usize,
+        target: u8,
+        max_minutes: usize,
+    ) -> Option<(World, usize)> {
+        let walk = self
+            .dist
+            .dist(world.current_node as usize, target as usize)?;
+        let minutes = world.minutes + walk as usize + 1;
+        if minutes > max_minutes {
+            return None;
+        }
+        let mut activated = world.activated;
+        activated.insert(target);
+        let new_world = World {
+            activated,
+            minutes,
+            current_node: target,
+        };
+        Some((
+            new_world,
+            score + (max_minutes - minutes) * self.rates[target as usize],
+        ))
+    }
+
+    fn start_world(&self) -> World {
+        World {
+            activated: U8Set::new(50),
+            minutes: 0,
+            current_node: self.start,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct World {
+    activated: U8Set,
+    minutes: usize,
+    current_node: u8,
+}
+
+fn part1(valves: &Valves, ctx: &Context) -> Result<usize> {
+    let all_non_zero_valves = valves.worth_opening();
+    let mut todo: VecDeque<(World, usize)> = VecDeque::new();
+    let mut best: HashMap<V<u8>, usize> = HashMap::default();
+    todo.push_back((valves.start_world(), 0));
+    let mut best_score = 0;
+    let mut c = 0;
+    while let Some((world, score)) = todo.pop_front() {
+        c += 1;
+        if c % 1024 == 0 {
+            ctx.check()?;
+            ctx.report(|| Progress {
+                states: c as u64,
+                frontier: todo.len() as u64,
+                best: Some(best_score as i64),
+                pruned: 0,
+            });
+        }
+        if best_score < score {
+            best_score = best_score.max(score);
+        }
+        let targets = all_non_zero_valves.difference(&world.activated);
+        for target in targets.iter() {
+            let Some((new_world, new_score)) = valves.open(&world, score, target, 30) else {
+                continue;
+            };
+            let mut new_activated_vec: V<u8> = new_world.activated.iter().collect();
+            new_activated_vec.sort_unstable();
+            let old_score = best.get(&new_activated_vec).copied().unwrap_or(0);
+            if old_score > new_score {
+            } else {
+                best.insert(new_activated_vec, new_score);
+            }
+            todo.push_back((new_world, new_score));
+        }
+    }
+
+    Ok(best_score)
+}
+
+fn part2(valves: &Valves, ctx: &Context) -> Result<usize> {
+    let all_non_zero_valves = valves.worth_opening();
+    let start_state = valves.start_world();
+    #[derive(Debug, PartialEq, Eq)]
+    struct Order(World, World, usize, usize);
+    impl Ord for Order {
+        fn cmp(&self, other: &Self) -> Ordering {
+            let ret = (self.2 + self.3).cmp(&(other.2 + other.3));
+            if ret == Ordering::Equal {
+                (other.0.minutes + other.1.minutes).cmp(&(self.0.minutes + self.1.minutes))
+            } else {
+                ret
+            }
+        }
+    }
+    impl PartialOrd for Order {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    let mut todo: BinaryHeap<Order> = Default::default();
+    let mut best: HashMap<V<u8>, usize> = HashMap::default();
+    todo.push(Order(start_state.clone(), start_state, 0, 0));
+    let mut best_score = 0;
+    let mut c = 0;
+    let mut rejected = 0;
+    while let Some(Order(my_world, elephant_world, my_score, elephant_score)) = todo.pop() {
+        c += 1;
+        if c % 1024 == 0 {
+            ctx.check()?;
+            ctx.report(|| Progress {
+                states: c as u64,
+                frontier: todo.len() as u64,
+                best: Some(best_score as i64),
+                pruned: rejected,
+            });
+        }
+        if best_score < (my_score + elephant_score) {
+            best_score = my_score + elephant_score;
+        }
+        let mut my_targets: U8Set = all_non_zero_valves.difference(&my_world.activated);
+        my_targets.max = 50;
+        let mut targets: U8Set = my_targets.difference(&elephant_world.activated);
+        targets.max = 50;
+        for (my_target, elephant_target) in iproduct!(targets.iter_clone(), targets.iter_clone()) {
+            if my_target == elephant_target {
+                continue;
+            }
+
+            let my_new_world = valves.open(&my_world, my_score, my_target, 26);
+            let elephant_new_world =
+                valves.open(&elephant_world, elephant_score, elephant_target, 26);
+            if my_new_world.is_none() && elephant_new_world.is_none() {
+                continue;
+            }
+            let (my_new_world, my_score) =
+                my_new_world.unwrap_or_else(|| (my_world.clone(), my_score));
+            let (elephant_new_world, elephant_score) =
+                elephant_new_world.unwrap_or_else(|| (elephant_world.clone(), elephant_score));
+
+            let my_new_activated = my_new_world.activated.iter();
+            let e_new_activated = elephant_new_world.activated.iter();
+            let mut all_new_activated: V<u8> = my_new_activated.chain(e_new_activated).collect();
+            all_new_activated.sort_unstable();
+            assert!(!all_new_activated.spilled(), "{}", all_new_activated.len());
+            let best_score_for_all = best.get(&all_new_activated).copied().unwrap_or(0);
+            let new_total_score = my_score + elephant_score;
+            if new_total_score >= best_score_for_all {
+                if new_total_score > best_score_for_all {
+                    best.insert(all_new_activated, new_total_score);
+                }
+                todo.push(Order(
+                    my_new_world,
+                    elephant_new_world,
+                    my_score,
+                    elephant_score,
+                ));
+            } else {
+                rejected += 1;
+            }
+        }
+    }
+
+    Ok(best_score)
+}
+
+pub struct Input {
+    valves: Valves,
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let input: HashMap<String, (i8, V<String>)> =
+            try_lines(input, parse)?.into_iter().collect();
+        if input.len() > 256 {
+            bail!("expected at most 256 valves, got {}", input.len());
+        }
+        let m: HashMap<String, u8> = input
+            .keys()
+            .enumerate()
+            .map(|(i, s)| (s.clone(), i as u8))
+            .collect();
+        let input: HashMap<u8, (i8, V<u8>)> = input
+            .iter()
+            .map(|(k, (rate, out))| {
+                let out = out
+                    .iter()
+                    .map(|s| m.get(s).copied())
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| anyhow!("valve {k} leads to a valve that doesn't exist"))?;
+                Ok((m[k], (*rate, out)))
+            })
+            .collect::<Result<_>>()?;
+
+        let mut g: Vec<(i8, V<u8>)> = vec![];
+        for i in input.keys().sorted() {
+            g.push(input[i].clone());
+        }
+
+        let start_node = *m.get("AA").ok_or_else(|| anyhow!("no valve AA"))?;
+        Ok(Input {
+            valves: Valves::new(&g, start_node)?,
+        })
+    }
+
+    fn part1(input: &Self::Input, ctx: &Context) -> Result<Answer> {
+        // Works but is tragically slow
+        Ok(part1(&input.valves, ctx)?.into())
+    }
+
+    fn part2(input: &Self::Input, ctx: &Context) -> Result<Answer> {
+        Ok(part2(&input.valves, ctx)?.into())
+    }
+}
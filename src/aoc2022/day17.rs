@@ -1,12 +1,15 @@
-use anyhow::Result;
-use rustc_hash::FxHashSet as HashSet;
+use anyhow::{anyhow, Result};
+use once_cell::sync::OnceCell;
 use smallvec::{smallvec, SmallVec};
-use std::time::{Duration, Instant};
+
+use crate::geom::Point2;
+use crate::grid::Grid;
+use crate::solution::{Answer, Context, Solution};
 
 type V<T> = SmallVec<[T; 5]>;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
-struct Pos {
+pub struct Pos {
     row: i64,
     col: i64,
 }
@@ -18,17 +21,20 @@ impl Pos {
     }
 }
 
+// Cell of the tower, which the grid keeps upside down.
+fn cell(p: Pos) -> Point2<usize> {
+    Point2::new(p.col as usize, p.row as usize)
+}
+
 const UP: Pos = Pos { row: 1, col: 0 };
 const DOWN: Pos = Pos { row: -1, col: 0 };
 const RIGHT: Pos = Pos { row: 0, col: 1 };
 
-fn parse(b: u8) -> Pos {
-    if b == b'<' {
-        Pos { row: 0, col: -1 }
-    } else if b == b'>' {
-        Pos { row: 0, col: 1 }
-    } else {
-        panic!()
+fn parse(b: u8) -> Result<Pos> {
+    match b {
+        b'<' => Ok(Pos { row: 0, col: -1 }),
+        b'>' => Ok(Pos { row: 0, col: 1 }),
+        _ => Err(anyhow!("expected '<' or '>', got '{}'", b as char)),
     }
 }
 
@@ -102,7 +108,8 @@ fn square() -> Shape {
 
 #[derive(Debug)]
 struct Game {
-    m: HashSet<Pos>,
+    // Settled rocks, row 0 is the bottom. Grows as the tower does.
+    m: Grid<bool>,
     active: Option<Shape>,
     top: i64,
 }
@@ -110,7 +117,7 @@ struct Game {
 impl Game {
     fn new() -> Self {
         Game {
-            m: Default::default(),
+            m: Grid::new(7, 0, false),
             active: None,
             top: -1,
         }
@@ -119,7 +126,12 @@ impl Game {
     fn finalize(&mut self) {
         if let Some(s) = &mut self.active {
             self.top = self.top.max(s.0.iter().map(|p| p.row).max().unwrap());
-            self.m.extend(&s.0);
+            for p in &s.0 {
+                while self.m.height() as i64 <= p.row {
+                    self.m.push_row([false; 7]);
+                }
+                self.m[cell(*p)] = true;
+            }
             self.active = None;
         }
     }
@@ -153,7 +165,7 @@ impl Game {
         if p.row < 0 {
             return false;
         }
-        !self.m.contains(&p)
+        !self.m.get(cell(p)).copied().unwrap_or(false)
     }
 
     fn turn(&mut self, dir: Pos) -> bool {
@@ -169,7 +181,7 @@ impl Game {
     }
 }
 
-fn run(wind: &[Pos], max: usize) -> Vec<i64> {
+fn run(wind: &[Pos], max: usize, ctx: &Context) -> Result<Vec<i64>> {
     let mut g = Game::new();
 
     let shapes = vec![line(), cross(), l(), line_down(), square()];
@@ -191,6 +203,7 @@ fn run(wind: &[Pos], max: usize) -> Vec<i64> {
             if next_shape == max {
                 break;
             }
+            ctx.check()?;
             g.finalize();
             let next_h = g.highest();
             hights.push(next_h + 1);
@@ -198,7 +211,7 @@ fn run(wind: &[Pos], max: usize) -> Vec<i64> {
             next_shape += 1;
         }
     }
-    hights
+    Ok(hights)
 }
 
 fn find_cycle(heights: &[i64]) -> (i64, i64) {
@@ -218,31 +231,54 @@ fn find_cycle(heights: &[i64]) -> (i64, i64) {
     unreachable!()
 }
 
-pub fn solve(input: &str, verify_expected: bool, output: bool) -> Result<Duration> {
-    let wind: Vec<Pos> = input.lines().next().unwrap().bytes().map(parse).collect();
-
-    let s = Instant::now();
+pub struct Input {
+    wind: Vec<Pos>,
+    // Height of the tower after every rock up to the 10000th, enough for part 2 to find a
+    // cycle. Built by whichever part runs first.
+    heights: OnceCell<Vec<i64>>,
+}
 
-    let heights = run(&wind, 10000);
-    let part1 = heights[2022];
-    let (cycle_width, delta) = find_cycle(&heights);
+impl Input {
+    fn heights(&self, ctx: &Context) -> Result<&[i64]> {
+        let heights = self
+            .heights
+            .get_or_try_init(|| run(&self.wind, 10000, ctx))?;
+        Ok(heights)
+    }
+}
 
-    let target = 1000000000000i64;
-    let times_full = target / cycle_width;
-    let diff_times = target - (times_full * cycle_width);
-    let base = times_full * delta;
-    let part2 = base + heights[diff_times as usize];
+pub struct Solver;
 
-    let e = s.elapsed();
+impl Solution for Solver {
+    type Input = Input;
 
-    if verify_expected {
-        assert_eq!(3193, part1);
-        assert_eq!(1577650429835, part2);
+    fn parse(input: &str) -> Result<Self::Input> {
+        let wind = input
+            .trim()
+            .bytes()
+            .map(parse)
+            .collect::<Result<Vec<_>>>()?;
+        if wind.is_empty() {
+            return Err(anyhow!("no jets"));
+        }
+        Ok(Input {
+            wind,
+            heights: OnceCell::new(),
+        })
     }
-    if output {
-        println!("\t{}", part1);
-        println!("\t{}", part2);
+
+    fn part1(input: &Self::Input, ctx: &Context) -> Result<Answer> {
+        Ok(input.heights(ctx)?[2022].into())
     }
 
-    Ok(e)
+    fn part2(input: &Self::Input, ctx: &Context) -> Result<Answer> {
+        let heights = input.heights(ctx)?;
+        let (cycle_width, delta) = find_cycle(heights);
+
+        let target = 1000000000000i64;
+        let times_full = target / cycle_width;
+        let diff_times = target - (times_full * cycle_width);
+        let base = times_full * delta;
+        Ok((base + heights[diff_times as usize]).into())
+    }
 }
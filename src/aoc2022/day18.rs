@@ -0,0 +1,91 @@
+use anyhow::{bail, Result};
+use itertools::Itertools;
+use rustc_hash::FxHashSet as HashSet;
+use std::ops::RangeInclusive;
+
+use crate::dijkstra::bfs_layers;
+use crate::geom::Point3;
+use crate::input::try_token_arrays;
+use crate::solution::{Answer, Context, Solution};
+
+type P3 = Point3<i32>;
+
+fn grow_from(
+    from: P3,
+    taken: &HashSet<P3>,
+    (x, y, z): (
+        RangeInclusive<i32>,
+        RangeInclusive<i32>,
+        RangeInclusive<i32>,
+    ),
+) -> HashSet<P3> {
+    // Points outside of the ranges are reached, but not grown from.
+    let neighbours_of = |p: &P3| {
+        let inside = x.contains(&p.x) && y.contains(&p.y) && z.contains(&p.z);
+        p.neighbours6()
+            .filter(move |n| inside && !taken.contains(n))
+    };
+    bfs_layers([from], neighbours_of).flatten().collect()
+}
+
+fn count_exposed_sides(ps: &[P3]) -> usize {
+    let points: HashSet<P3> = ps.iter().copied().collect();
+    ps.iter()
+        .flat_map(|p| p.neighbours6())
+        .filter(|n| !points.contains(n))
+        .count()
+}
+
+fn count_outside_sides(ps: &[P3]) -> usize {
+    let (min_x, max_x) = ps.iter().map(|p| p.x).minmax().into_option().unwrap();
+    let (min_y, max_y) = ps.iter().map(|p| p.y).minmax().into_option().unwrap();
+    let (min_z, max_z) = ps.iter().map(|p| p.z).minmax().into_option().unwrap();
+
+    let points: HashSet<P3> = ps.iter().copied().collect();
+
+    let outside = P3 {
+        x: min_x - 1,
+        y: min_y - 1,
+        z: min_z - 1,
+    };
+
+    let outside_points = grow_from(
+        outside,
+        &points,
+        (
+            ((min_x - 1)..=(max_x + 1)),
+            ((min_y - 1)..=(max_y + 1)),
+            ((min_z - 1)..=(max_z + 1)),
+        ),
+    );
+
+    ps.iter()
+        .flat_map(|p| p.neighbours6())
+        .filter(|p| !points.contains(p) && outside_points.contains(p))
+        .count()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<P3>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let cubes: Vec<P3> = try_token_arrays(input, "\n", Some(","))?
+            .into_iter()
+            .map(|[x, y, z]| P3 { x, y, z })
+            .collect();
+        if cubes.is_empty() {
+            bail!("no cubes");
+        }
+        Ok(cubes)
+    }
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        Ok(count_exposed_sides(input).into())
+    }
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        Ok(count_outside_sides(input).into())
+    }
+}
@@ -1,15 +1,14 @@
-use anyhow::Result;
-use maplit::hashmap;
+use anyhow::{anyhow, bail, Result};
 use rayon::prelude::*;
 use rustc_hash::FxHashMap as HashMap;
 use smallvec::{smallvec, SmallVec};
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 use std::hash::{Hash, Hasher};
-use std::str::FromStr;
-use std::time::{Duration, Instant};
 
-use crate::input::tokens;
+use crate::input::try_lines;
+use crate::progress::Progress;
+use crate::solution::{Answer, Context, Solution};
 
 type V<T> = SmallVec<[T; 5]>;
 
@@ -90,7 +89,8 @@ fn find_best(
     max_time: u8,
     start: &[Inner],
     keep_last: bool,
-) -> (usize, Vec<Inner>) {
+    ctx: &Context,
+) -> Result<(usize, Vec<Inner>)> {
     // let mut todo: VecDeque<Reverse<Inner>> = VecDeque::new();
     let mut todo: BinaryHeap<Reverse<Inner>> = Default::default();
     let mut seen: HashMap<State, u8> = Default::default();
@@ -101,7 +101,19 @@ fn find_best(
     let mut all_last = vec![];
     let mut best_score = 0;
 
+    let mut c = 0;
+    let mut pruned = 0;
     while let Some(Reverse(inner)) = todo.pop() {
+        c += 1;
+        if c % 1024 == 0 {
+            ctx.check()?;
+            ctx.report(|| Progress {
+                states: c as u64,
+                frontier: todo.len() as u64,
+                best: Some(best_score as i64),
+                pruned,
+            });
+        }
         if inner.time == max_time {
             if keep_last {
                 all_last.push(inner);
@@ -121,6 +133,7 @@ fn find_best(
                 .copied()
                 .unwrap_or(u8::max_value());
             if t <= next_inner.time {
+                pruned += 1;
                 continue;
             } else {
                 seen.insert(next_inner.state, next_inner.time);
@@ -129,23 +142,15 @@ fn find_best(
         }
     }
 
-    (best_score as usize, all_last)
+    Ok((best_score as usize, all_last))
 }
 
 #[derive(Debug)]
-struct Blueprint {
+pub struct Blueprint {
     id: usize,
     cost: [[u8; 4]; 4],
 }
 
-impl FromStr for Blueprint {
-    type Err = ();
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        Ok(parse(s))
-    }
-}
-
 impl Blueprint {
     #[allow(unused)]
     fn dbg(&self, m: &HashMap<&str, u8>) {
@@ -170,28 +175,41 @@ impl Blueprint {
     }
 }
 
-fn parse(s: &str) -> Blueprint {
-    let m: HashMap<&str, u8> = hashmap! {
-        "clay" => CLAY,
-        "obsidian" => OBSIDIAN,
-        "ore" => ORE,
-        "geode" => GEODE,
-    }
-    .into_iter()
-    .collect();
+// Every blueprint reads like this, with its own numbers in place of the Ns.
+const TEMPLATE: &str = "Blueprint N: Each ore robot costs N ore. Each clay robot costs N ore. \
+    Each obsidian robot costs N ore and N clay. Each geode robot costs N ore and N obsidian.";
 
+fn parse(s: &str) -> Result<Blueprint> {
     let s = s.replace('.', "");
-    let s: Vec<_> = s.split(' ').collect();
-    let id: usize = s[1].strip_suffix(':').unwrap().parse().unwrap();
+    let words: Vec<_> = s.split(' ').collect();
+    let template = TEMPLATE.replace('.', "");
+    let template: Vec<_> = template.split_whitespace().collect();
+    if words.len() != template.len() {
+        bail!("expected {} words, got {}", template.len(), words.len());
+    }
+
+    let mut n = vec![];
+    for (i, (word, expected)) in words.iter().zip(&template).enumerate() {
+        if let Some((prefix, suffix)) = expected.split_once('N') {
+            let number = word
+                .strip_prefix(prefix)
+                .and_then(|w| w.strip_suffix(suffix))
+                .and_then(|w| w.parse::<usize>().ok())
+                .ok_or_else(|| anyhow!("expected a number at word {i}"))?;
+            n.push(number);
+        } else if word != expected {
+            bail!("expected '{expected}' at word {i}");
+        }
+    }
 
     let mut cost = [[0; 4]; 4];
-    cost[ORE as usize][*m.get(s[7]).unwrap() as usize] = s[6].parse().unwrap();
-    cost[CLAY as usize][*m.get(s[13]).unwrap() as usize] = s[12].parse().unwrap();
-    cost[OBSIDIAN as usize][*m.get(s[19]).unwrap() as usize] = s[18].parse().unwrap();
-    cost[OBSIDIAN as usize][*m.get(s[22]).unwrap() as usize] = s[21].parse().unwrap();
-    cost[GEODE as usize][*m.get(s[28]).unwrap() as usize] = s[27].parse().unwrap();
-    cost[GEODE as usize][*m.get(s[31]).unwrap() as usize] = s[30].parse().unwrap();
-    Blueprint { id, cost }
+    cost[ORE as usize][ORE as usize] = n[1].try_into()?;
+    cost[CLAY as usize][ORE as usize] = n[2].try_into()?;
+    cost[OBSIDIAN as usize][ORE as usize] = n[3].try_into()?;
+    cost[OBSIDIAN as usize][CLAY as usize] = n[4].try_into()?;
+    cost[GEODE as usize][ORE as usize] = n[5].try_into()?;
+    cost[GEODE as usize][OBSIDIAN as usize] = n[6].try_into()?;
+    Ok(Blueprint { id: n[0], cost })
 }
 
 fn generate_resources(mut state: State) -> State {
@@ -201,41 +219,64 @@ fn generate_resources(mut state: State) -> State {
     state
 }
 
-fn compute_part2(blueprint: &Blueprint) -> usize {
-    let (ret, mut all_last) = find_best(blueprint, 24, &[Inner::new()], true);
+fn compute_part2(blueprint: &Blueprint, ctx: &Context) -> Result<usize> {
+    let (ret, mut all_last) = find_best(blueprint, 24, &[Inner::new()], true, ctx)?;
     all_last.sort_unstable_by_key(|inner| Reverse(inner.state.current_score()));
     if ret > 0 {
         all_last.truncate(all_last.len() / 10);
     }
-    find_best(blueprint, 32, &all_last, false).0
+    Ok(find_best(blueprint, 32, &all_last, false, ctx)?.0)
 }
 
-pub fn solve(input: &str, verify_expected: bool, output: bool) -> Result<Duration> {
-    let input: Vec<Blueprint> = tokens(input, Some("\n"));
-    // let input: Vec<Blueprint> = input.lines().map(|s| parse(s)).collect();
+pub struct Solver;
 
-    let s = Instant::now();
+impl Solution for Solver {
+    type Input = Vec<Blueprint>;
 
-    let part1 = input
-        .par_iter()
-        .map(|b| b.id * find_best(b, 24, &[Inner::new()], false).0)
-        .sum::<usize>();
-    assert_eq!(1395, part1);
-    let part2 = input
-        .par_iter()
-        .take(3)
-        .map(compute_part2)
-        .product::<usize>();
+    fn parse(input: &str) -> Result<Self::Input> {
+        try_lines(input, parse)
+    }
 
-    let e = s.elapsed();
+    fn part1(input: &Self::Input, ctx: &Context) -> Result<Answer> {
+        let part1 = input
+            .par_iter()
+            .map(|b| Ok(b.id * find_best(b, 24, &[Inner::new()], false, ctx)?.0))
+            .sum::<Result<usize>>()?;
+        Ok(part1.into())
+    }
 
-    if verify_expected {
-        assert_eq!(1395, part1);
-        assert_eq!(2700, part2);
+    fn part2(input: &Self::Input, ctx: &Context) -> Result<Answer> {
+        let part2 = input
+            .par_iter()
+            .take(3)
+            .map(|b| compute_part2(b, ctx))
+            .product::<Result<usize>>()?;
+        Ok(part2.into())
     }
-    if output {
-        println!("\t{}", part1);
-        println!("\t{}", part2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let blueprint = "Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. \
+            Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+        let b = parse(blueprint).unwrap();
+        assert_eq!(2, b.id);
+        assert_eq!([3, 8, 0, 0], b.cost[OBSIDIAN as usize]);
+        assert_eq!(
+            "expected 'ore' at word 7",
+            parse(&blueprint.replacen("2 ore", "2 oar", 1))
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "expected a number at word 12",
+            parse(&blueprint.replace("3 ore", "x ore"))
+                .unwrap_err()
+                .to_string()
+        );
     }
-    Ok(e)
 }
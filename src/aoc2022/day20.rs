@@ -1,6 +1,8 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use std::collections::VecDeque;
-use std::time::{Duration, Instant};
+
+use crate::input::try_tokens;
+use crate::solution::{Answer, Context, Solution};
 
 fn run_once(deq: &mut VecDeque<(i64, i64)>, input: &[(i64, i64)]) {
     for (key, _) in input {
@@ -29,26 +31,25 @@ fn mix(vals: &[i64], times: usize) -> i64 {
         .sum::<i64>()
 }
 
-pub fn solve(input: &str, verify_expected: bool, output: bool) -> Result<Duration> {
-    let mut input: Vec<i64> = input.lines().map(|s| s.parse().unwrap()).collect();
-
-    let s = Instant::now();
-
-    let part1 = mix(&input, 1);
+pub struct Solver;
 
-    input.iter_mut().for_each(|v| *v *= 811589153);
+impl Solution for Solver {
+    type Input = Vec<i64>;
 
-    let part2 = mix(&input, 10);
-
-    let e = s.elapsed();
+    fn parse(input: &str) -> Result<Self::Input> {
+        let numbers: Vec<i64> = try_tokens(input, Some("\n"))?;
+        if !numbers.contains(&0) {
+            bail!("expected a 0 among the numbers");
+        }
+        Ok(numbers)
+    }
 
-    if verify_expected {
-        assert_eq!(4578, part1);
-        assert_eq!(2159638736133, part2);
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        Ok(mix(input, 1).into())
     }
-    if output {
-        println!("\t{}", part1);
-        println!("\t{}", part2);
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let input: Vec<i64> = input.iter().map(|v| v * 811589153).collect();
+        Ok(mix(&input, 10).into())
     }
-    Ok(e)
 }
@@ -1,24 +1,34 @@
-use anyhow::{Error, Result};
+use anyhow::{anyhow, bail, Error, Result};
 use itertools::Itertools;
 use rustc_hash::FxHashMap as HashMap;
 use smallvec::SmallVec;
 use std::str::FromStr;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::SeqCst;
-use std::time::{Duration, Instant};
+
+use crate::input::try_lines;
+use crate::solution::{Answer, Context, Solution};
 
 type V<T> = SmallVec<[T; 10]>;
 type State = Vec<Expr>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Node(usize);
+pub struct Node(usize);
 
 static ROOT: AtomicUsize = AtomicUsize::new(0);
 static HUMN: AtomicUsize = AtomicUsize::new(0);
 
 impl Node {
-    fn new(s: &str) -> Self {
-        Self(u32::from_le_bytes(s.as_bytes().try_into().unwrap()) as usize)
+    fn new(s: &str) -> Result<Self> {
+        let name: [u8; 4] = s
+            .as_bytes()
+            .try_into()
+            .map_err(|_| anyhow!("expected a name of 4 letters, got '{s}'"))?;
+        Ok(Self(u32::from_le_bytes(name) as usize))
+    }
+
+    fn name(&self) -> String {
+        String::from_utf8_lossy(&(self.0 as u32).to_le_bytes()).into_owned()
     }
 }
 
@@ -29,7 +39,7 @@ impl From<&AtomicUsize> for Node {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Kind {
+pub enum Kind {
     Add,
     Sub,
     Mul,
@@ -59,13 +69,13 @@ impl FromStr for Kind {
             "*" => Ok(Mul),
             "/" => Ok(Div),
             "=" => Ok(Eql),
-            _ => unreachable!(),
+            _ => Err(anyhow!("unknown operation '{s}'")),
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Expr {
+pub enum Expr {
     Var,
     Const(i64),
     Op(Node, Kind, Node),
@@ -81,18 +91,17 @@ impl Expr {
 
 use Expr::*;
 
-fn parse(s: &str) -> (Node, Expr) {
-    let s = s.split(' ').collect_vec();
-    let name = s[0].strip_suffix(':').unwrap();
-    let expr = if s.len() == 2 {
-        Const(s[1].parse().unwrap())
-    } else {
-        let l = Node::new(s[1]);
-        let op = s[2].parse().unwrap();
-        let r = Node::new(s[3]);
-        Op(l, op, r)
+fn parse(s: &str) -> Result<(Node, Expr)> {
+    let expr = match s.split(' ').collect_vec()[..] {
+        [_, n] => Const(n.parse()?),
+        [_, l, op, r] => Op(Node::new(l)?, op.parse()?, Node::new(r)?),
+        _ => bail!("expected 'name: N' or 'name: a op b'"),
     };
-    (Node::new(name), expr)
+    let name = s
+        .split_once(": ")
+        .map(|(name, _)| name)
+        .ok_or_else(|| anyhow!("expected a name followed by ': '"))?;
+    Ok((Node::new(name)?, expr))
 }
 
 fn eval(mut state: State) -> State {
@@ -184,56 +193,68 @@ fn simplify(mut state: State) -> State {
     state
 }
 
-fn fix(e: Expr, m: &HashMap<Node, usize>) -> Expr {
-    match e {
+// Replaces the names in `e` with indices of the monkeys.
+fn fix(e: Expr, m: &HashMap<Node, usize>) -> Result<Expr> {
+    let index = |n: Node| {
+        m.get(&n)
+            .map(|i| Node(*i))
+            .ok_or_else(|| anyhow!("unknown monkey '{}'", n.name()))
+    };
+    Ok(match e {
         Var => Var,
         Const(n) => Const(n),
-        Op(l, op, r) => Op(Node(*m.get(&l).unwrap()), op, Node(*m.get(&r).unwrap())),
-    }
+        Op(l, op, r) => Op(index(l)?, op, index(r)?),
+    })
 }
 
-pub fn solve(input: &str, verify_expected: bool, output: bool) -> Result<Duration> {
-    let input: Vec<(Node, Expr)> = input.lines().map(parse).collect();
-    let m: HashMap<Node, usize> = input
-        .iter()
-        .enumerate()
-        .map(|(i, (n, _))| (*n, i))
-        .collect();
-    let input: Vec<Expr> = input.into_iter().map(|(_, e)| fix(e, &m)).collect();
-
-    let s = Instant::now();
-    ROOT.store(m.get(&Node::new("root")).copied().unwrap(), SeqCst);
-    HUMN.store(m.get(&Node::new("humn")).copied().unwrap(), SeqCst);
-
-    let state = eval(input.clone());
-    let part1 = match state[Node::from(&ROOT).0] {
-        Const(n) => n,
-        _ => unreachable!(),
-    };
+pub struct Solver;
 
-    let mut state = input;
-    state[Node::from(&HUMN).0] = Var;
-    state[Node::from(&ROOT).0].make_op(Eql);
-    state = simplify(state);
+impl Solution for Solver {
+    type Input = Vec<Expr>;
 
-    let part2 = match state[Node::from(&ROOT).0] {
-        Op(l, Eql, r) => match (state[l.0], state[r.0]) {
-            (Const(n), _) => n,
-            (_, Const(n)) => n,
-            _ => unreachable!(),
-        },
-        _ => unreachable!(),
-    };
+    fn parse(input: &str) -> Result<Self::Input> {
+        let input: Vec<(Node, Expr)> = try_lines(input, parse)?;
+        let m: HashMap<Node, usize> = input
+            .iter()
+            .enumerate()
+            .map(|(i, (n, _))| (*n, i))
+            .collect();
+        let input: Vec<Expr> = input
+            .into_iter()
+            .map(|(_, e)| fix(e, &m))
+            .collect::<Result<_>>()?;
 
-    let e = s.elapsed();
+        let root = Node::new("root")?;
+        let humn = Node::new("humn")?;
+        ROOT.store(*m.get(&root).ok_or_else(|| anyhow!("no root"))?, SeqCst);
+        HUMN.store(*m.get(&humn).ok_or_else(|| anyhow!("no humn"))?, SeqCst);
 
-    if verify_expected {
-        assert_eq!(21208142603224, part1);
-        assert_eq!(3882224466191, part2);
+        Ok(input)
     }
-    if output {
-        println!("\t{}", part1);
-        println!("\t{}", part2);
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let state = eval(input.clone());
+        let part1 = match state[Node::from(&ROOT).0] {
+            Const(n) => n,
+            _ => unreachable!(),
+        };
+        Ok(part1.into())
+    }
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let mut state = input.clone();
+        state[Node::from(&HUMN).0] = Var;
+        state[Node::from(&ROOT).0].make_op(Eql);
+        state = simplify(state);
+
+        let part2 = match state[Node::from(&ROOT).0] {
+            Op(l, Eql, r) => match (state[l.0], state[r.0]) {
+                (Const(n), _) => n,
+                (_, Const(n)) => n,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        Ok(part2.into())
     }
-    Ok(e)
 }
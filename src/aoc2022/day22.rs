@@ -0,0 +1,472 @@
+use anyhow::{anyhow, bail, Result};
+use itertools::{iproduct, Itertools};
+use rustc_hash::FxHashMap as HashMap;
+use rustc_hash::FxHashSet as HashSet;
+use std::collections::BTreeMap;
+
+use crate::geom::{Dir4, Point2};
+use crate::grid::Grid;
+use crate::input::try_lines;
+use crate::solution::{Answer, Context, Solution};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Step {
+    Move(i32),
+    Left,
+    Right,
+}
+use Step::*;
+
+type I = i32;
+
+fn parse_path(s: &str) -> Result<Vec<Step>> {
+    let s = s.replace('R', " R ").replace('L', " L ");
+    s.split(' ')
+        .filter(|s| !s.is_empty())
+        .map(|s| match s {
+            "L" => Ok(Left),
+            "R" => Ok(Right),
+            n => n
+                .parse()
+                .map(Move)
+                .map_err(|_| anyhow!("expected a number, 'L' or 'R' in the path, got '{n}'")),
+        })
+        .collect()
+}
+
+// Column (x) and row (y) on the map, or of a side among the others.
+type P = Point2<I>;
+
+fn get(p: P, m: &Grid<u8>) -> Option<u8> {
+    if p.x < 0 || p.y < 0 {
+        None
+    } else {
+        m.get(Point2::new(p.x as usize, p.y as usize)).copied()
+    }
+}
+
+fn dir_of(offset: P) -> Dir4 {
+    Dir4::ALL
+        .into_iter()
+        .find(|dir| dir.offset() == offset)
+        .unwrap()
+}
+
+fn next2(
+    pos: P,
+    dir: Dir4,
+    m: &Grid<u8>,
+    cube: &HashMap<(P3, P3), (u8, P)>,
+    p_to_p3: &HashMap<P, P3>,
+    orient_per_p: &HashMap<P, Orient>,
+) -> Option<(P, Dir4)> {
+    let next = pos + dir.offset();
+    match get(next, m) {
+        Some(b'.') => Some((next, dir)),
+        Some(b'#') => None,
+        _ => {
+            let p3 = p_to_p3.get(&pos).unwrap();
+            let orient = orient_per_p.get(&pos).unwrap();
+            let next_normal = match dir {
+                Dir4::Up => orient.down.inv(),
+                Dir4::Down => orient.down,
+                Dir4::Left => orient.right.inv(),
+                Dir4::Right => orient.right,
+            };
+            let next_from_p3 = p3.add(orient.normal.inv());
+            let next_from_p3_as_p: P = cube.get(&(next_from_p3, next_normal)).unwrap().1;
+            let (value, next_p2) = cube.get(&(*p3, next_normal)).unwrap();
+            let new_dir = dir_of(next_from_p3_as_p - *next_p2);
+            match value {
+                b'.' => Some((*next_p2, new_dir)),
+                b'#' => None,
+                _ => todo!(),
+            }
+        }
+    }
+}
+
+fn next(pos: P, dir: Dir4, m: &Grid<u8>) -> Option<P> {
+    let next = pos + dir.offset();
+    match get(next, m) {
+        Some(b'.') => Some(next),
+        Some(b'#') => None,
+        _ => {
+            let mut cur = match dir {
+                Dir4::Right => P::new(0, pos.y),
+                Dir4::Down => P::new(pos.x, 0),
+                Dir4::Left => P::new((m.width() - 1) as I, pos.y),
+                Dir4::Up => P::new(pos.x, (m.height() - 1) as I),
+            };
+
+            loop {
+                let cand = get(cur, m).unwrap();
+                if cand == b'.' {
+                    return Some(cur);
+                } else if cand == b'#' {
+                    return None;
+                }
+                cur += dir.offset();
+            }
+        }
+    }
+}
+
+fn find_start(m: &Grid<u8>) -> P {
+    let col = m.row(0).iter().position(|b| *b == b'.').unwrap();
+    P::new(col as I, 0)
+}
+
+fn password(pos: P, dir: Dir4) -> usize {
+    // Right is 0, clockwise.
+    let facing = (dir as I + 3) % 4;
+    ((pos.y + 1) * 1000 + (pos.x + 1) * 4 + facing) as usize
+}
+
+fn run_path(m: &Grid<u8>, path: &[Step]) -> usize {
+    let mut pos: P = find_start(m);
+    let mut dir = Dir4::Right;
+    for step in path {
+        match step {
+            Left => dir = dir.left(),
+            Right => dir = dir.right(),
+            Move(n) => {
+                for _ in 0..*n {
+                    if let Some(next) = next(pos, dir, m) {
+                        pos = next;
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    password(pos, dir)
+}
+
+fn run_path2(
+    m: &Grid<u8>,
+    path: &[Step],
+    cube: &HashMap<(P3, P3), (u8, P)>,
+    p_to_p3: &HashMap<P, P3>,
+    orient_per_p: &HashMap<P, Orient>,
+) -> usize {
+    let mut pos: P = find_start(m);
+    let mut dir = Dir4::Right;
+    for step in path {
+        match step {
+            Left => dir = dir.left(),
+            Right => dir = dir.right(),
+            Move(n) => {
+                for _ in 0..*n {
+                    if let Some((next, next_dir)) = next2(pos, dir, m, cube, p_to_p3, orient_per_p)
+                    {
+                        pos = next;
+                        dir = next_dir;
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    password(pos, dir)
+}
+
+// Side pos -> Map pos
+type Side = HashMap<P, P>;
+
+fn get_side_from(start: P, size: usize, m: &Grid<u8>) -> Option<Side> {
+    let mut ret: HashMap<P, P> = Default::default();
+    for y in start.y..start.y + size as I {
+        for x in start.x..start.x + size as I {
+            let map_pos = P::new(x, y);
+            if let Some(c) = get(map_pos, m) {
+                if c == b' ' {
+                    return None;
+                }
+                ret.insert(map_pos - start, map_pos);
+            }
+        }
+    }
+    if ret.is_empty() {
+        None
+    } else {
+        Some(ret)
+    }
+}
+
+fn split_into_sides(m: &Grid<u8>, size: usize) -> BTreeMap<P, Side> {
+    iproduct!(0..4, 0..4)
+        .map(|(row, col)| P::new((col * size) as I, (row * size) as I))
+        .flat_map(|start| {
+            get_side_from(start, size, m)
+                .map(|side| (P::new(start.x / size as I, start.y / size as I), side))
+        })
+        .collect()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct P3 {
+    x: I,
+    y: I,
+    z: I,
+}
+
+impl P3 {
+    #[allow(unused)]
+    fn name(self) -> String {
+        if self == UP3 {
+            return "UP".to_owned();
+        }
+        if self == DOWN3 {
+            return "DOWN".to_owned();
+        }
+        if self == LEFT3 {
+            return "LEFT".to_owned();
+        }
+        if self == RIGHT3 {
+            return "RIGHT".to_owned();
+        }
+        if self == IN3 {
+            return "IN".to_owned();
+        }
+        if self == OUT3 {
+            return "OUT".to_owned();
+        }
+        format!("{self:?}")
+    }
+    fn add(self, o: Self) -> P3 {
+        P3 {
+            x: self.x + o.x,
+            y: self.y + o.y,
+            z: self.z + o.z,
+        }
+    }
+    fn inv(self) -> P3 {
+        P3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+    fn cross(self, o: P3) -> P3 {
+        let mut ret: P3 = P3 { x: 0, y: 0, z: 0 };
+        ret.x = self.y * o.z - self.z * o.y;
+        ret.y = -(self.x * o.z - self.z * o.x);
+        ret.z = self.x * o.y - self.y * o.x;
+        ret
+    }
+}
+
+const UP3: P3 = P3 { x: 0, y: -1, z: 0 };
+const DOWN3: P3 = P3 { x: 0, y: 1, z: 0 };
+const LEFT3: P3 = P3 { x: -1, y: 0, z: 0 };
+const RIGHT3: P3 = P3 { x: 1, y: 0, z: 0 };
+const IN3: P3 = P3 { x: 0, y: 0, z: -1 };
+const OUT3: P3 = P3 { x: 0, y: 0, z: 1 };
+
+#[derive(Debug, Clone, Copy)]
+struct Orient {
+    down: P3,
+    right: P3,
+    normal: P3,
+}
+
+impl Orient {
+    #[allow(unused)]
+    fn print(&self) -> String {
+        format!(
+            "Orient{{down: {}, right: {}, normal: {}}}",
+            self.down.name(),
+            self.right.name(),
+            self.normal.name()
+        )
+    }
+    // Cube position of the side's top left corner. The cube spans 0..=max on x and y and
+    // -max..=0 on z, so going right and down from the corner has to stay within it.
+    fn corner(&self, max: I) -> P3 {
+        let pick = |normal: I, along: I, lo: I, hi: I| {
+            if normal > 0 || (normal == 0 && along < 0) {
+                hi
+            } else {
+                lo
+            }
+        };
+        P3 {
+            x: pick(self.normal.x, self.down.x + self.right.x, 0, max),
+            y: pick(self.normal.y, self.down.y + self.right.y, 0, max),
+            z: pick(self.normal.z, self.down.z + self.right.z, -max, 0),
+        }
+    }
+    fn check(&self, name: &P) {
+        debug_assert_eq!(
+            self.normal,
+            self.down.cross(self.right),
+            "{name:?}: {:?}",
+            self
+        );
+        debug_assert_eq!(self.right, self.normal.cross(self.down));
+    }
+}
+
+fn find_normals_for_sides(sides: &BTreeMap<P, Side>) -> HashMap<P, Orient> {
+    let mut normals: HashMap<P, Orient> = Default::default();
+    normals.insert(
+        *sides.keys().find(|p| p.y == 0).unwrap(),
+        Orient {
+            down: IN3,
+            right: RIGHT3,
+            normal: UP3,
+        },
+    );
+    let mut todo: HashSet<P> = sides.keys().copied().collect();
+    todo.remove(sides.keys().find(|p| p.y == 0).unwrap());
+    while !todo.is_empty() {
+        normals.iter().for_each(|(p, o)| o.check(p));
+        let mut to_add = vec![];
+        let mut to_rem = vec![];
+        'out: for to_check in &todo {
+            for (done, orient) in &normals {
+                // Rolling the cube onto a neighbouring side rotates it around the axis
+                // perpendicular to the move.
+                let next = if *done + Dir4::Down.offset() == *to_check {
+                    Orient {
+                        normal: orient.down,
+                        down: orient.normal.inv(),
+                        right: orient.right,
+                    }
+                } else if *done + Dir4::Up.offset() == *to_check {
+                    Orient {
+                        normal: orient.down.inv(),
+                        down: orient.normal,
+                        right: orient.right,
+                    }
+                } else if *done + Dir4::Left.offset() == *to_check {
+                    Orient {
+                        normal: orient.right.inv(),
+                        down: orient.down,
+                        right: orient.normal,
+                    }
+                } else if *done + Dir4::Right.offset() == *to_check {
+                    Orient {
+                        normal: orient.right,
+                        down: orient.down,
+                        right: orient.normal.inv(),
+                    }
+                } else {
+                    continue;
+                };
+                to_add.push((*to_check, next));
+                to_rem.push(*to_check);
+                break 'out;
+            }
+        }
+        for (p, n) in to_add {
+            normals.insert(p, n);
+        }
+        for p in to_rem {
+            todo.remove(&p);
+        }
+    }
+    normals.iter().for_each(|(p, o)| o.check(p));
+    normals
+}
+
+fn create_cube(
+    map: &Grid<u8>,
+    sides: &BTreeMap<P, Side>,
+    normals: &HashMap<P, Orient>,
+    size: usize,
+) -> (HashMap<(P3, P3), (u8, P)>, HashMap<P, P3>) {
+    let max = size as I - 1;
+    let mut cube: HashMap<(P3, P3), (u8, P)> = Default::default();
+    let mut p_to_p3: HashMap<P, P3> = Default::default();
+    for (p, points) in sides {
+        let orient = normals.get(p).unwrap();
+        let mut row_start = orient.corner(max);
+        let (min_x, max_x) = points.keys().map(|p| p.x).minmax().into_option().unwrap();
+        let (min_y, max_y) = points.keys().map(|p| p.y).minmax().into_option().unwrap();
+        assert_eq!(0, min_x);
+        assert_eq!(size as I - 1, max_x);
+        assert_eq!(0, min_y);
+        assert_eq!(size as I - 1, max_y);
+        for row in 0..size {
+            let mut cube_pos = row_start;
+            for col in 0..size {
+                let tmp_pos = P::new(col as I, row as I);
+                let tmp_pos2 = points.get(&tmp_pos).unwrap();
+                let value = get(*tmp_pos2, map).unwrap();
+                cube.insert((cube_pos, orient.normal), (value, *tmp_pos2));
+                p_to_p3.insert(*tmp_pos2, cube_pos);
+                cube_pos = cube_pos.add(orient.right);
+            }
+            row_start = row_start.add(orient.down);
+        }
+    }
+    (cube, p_to_p3)
+}
+
+pub struct Input {
+    map: Grid<u8>,
+    path: Vec<Step>,
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (map, path) = input
+            .split_once("\n\n")
+            .ok_or_else(|| anyhow!("expected an empty line between the map and the path"))?;
+        let mut rows: Vec<Vec<u8>> =
+            try_lines(map, |l| match l.bytes().find(|b| !b" .#".contains(b)) {
+                Some(b) => Err(anyhow!("unexpected '{}' in the map", b as char)),
+                None => Ok(l.bytes().collect()),
+            })?;
+        let max_w = rows
+            .iter()
+            .map(|row| row.len())
+            .max()
+            .ok_or_else(|| anyhow!("empty map"))?;
+        for row in &mut rows {
+            row.resize(max_w, b' ');
+        }
+        let map = Grid::from_rows(rows)?;
+        if !map.row(0).contains(&b'.') {
+            bail!("no open tile to start from on the top row");
+        }
+        let path = parse_path(path.trim())?;
+        Ok(Input { map, path })
+    }
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        Ok(run_path(&input.map, &input.path).into())
+    }
+
+    fn part2(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let map = &input.map;
+        let tiles = map.iter().filter(|(_, b)| **b != b' ').count();
+        let size = ((tiles / 6) as f64).sqrt() as usize;
+        if size == 0 || 6 * size * size != tiles {
+            bail!("{tiles} tiles can't be folded into a cube of 6 square faces");
+        }
+
+        let sides: BTreeMap<P, Side> = split_into_sides(map, size);
+
+        // Side -> Normal
+        let normals: HashMap<P, Orient> = find_normals_for_sides(&sides);
+
+        let (cube, p_to_p3) = create_cube(map, &sides, &normals, size);
+
+        let orient_per_p: HashMap<P, Orient> = normals
+            .iter()
+            .flat_map(|(p, orient)| sides.get(p).map(|side| (side, orient)))
+            .flat_map(|(side, orient)| side.iter().map(|(_, map_pos)| (*map_pos, *orient)))
+            .collect();
+
+        Ok(run_path2(map, &input.path, &cube, &p_to_p3, &orient_per_p).into())
+    }
+}
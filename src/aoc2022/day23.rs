@@ -0,0 +1,191 @@
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+use once_cell::sync::OnceCell;
+use std::collections::VecDeque;
+
+use crate::geom::{Dir8, Dir8::*, Point2};
+use crate::grid::{Grid, Pos};
+use crate::solution::{Answer, Context, Solution};
+
+#[derive(Debug, Clone, Copy)]
+struct Rule {
+    free_dirs: [Dir8; 3],
+    move_dir: Dir8,
+}
+
+impl Rule {
+    fn passes(&self, mut non_empty_adjacent: impl Iterator<Item = Dir8>) -> bool {
+        non_empty_adjacent.all(|dir| !self.free_dirs.contains(&dir))
+    }
+}
+
+const RULES: [Rule; 4] = [
+    Rule {
+        free_dirs: [N, NE, NW],
+        move_dir: N,
+    },
+    Rule {
+        free_dirs: [S, SE, SW],
+        move_dir: S,
+    },
+    Rule {
+        free_dirs: [W, NW, SW],
+        move_dir: W,
+    },
+    Rule {
+        free_dirs: [E, NE, SE],
+        move_dir: E,
+    },
+];
+
+// Cells added on every side when an elf reaches the edge of the map.
+const MARGIN: usize = 8;
+
+// Map of the elves, which grows as they spread, and their positions on it.
+struct Elves {
+    map: Grid<bool>,
+    elves: Vec<Pos>,
+}
+
+impl Elves {
+    fn new(map: &Grid<bool>) -> Self {
+        let elves = map.iter().filter(|(_, e)| **e).map(|(p, _)| p).collect();
+        let mut elves = Self {
+            map: map.clone(),
+            elves,
+        };
+        elves.grow();
+        elves
+    }
+
+    // Pads the map so that no elf is on its edge, so all their neighbours are on it.
+    fn grow(&mut self) {
+        let (width, height) = (self.map.width(), self.map.height());
+        if self
+            .elves
+            .iter()
+            .any(|e| e.x == 0 || e.y == 0 || e.x == width - 1 || e.y == height - 1)
+        {
+            self.map = self.map.pad(MARGIN, false);
+            for elf in &mut self.elves {
+                *elf += Point2::new(MARGIN, MARGIN);
+            }
+        }
+    }
+
+    fn adjacent(&self, elf: Pos) -> impl Iterator<Item = Dir8> + '_ + Clone {
+        Dir8::ALL
+            .into_iter()
+            .filter(move |dir| self.map[self.map.offset(elf, dir.offset()).unwrap()])
+    }
+
+    // Moves the elves by a turn, returns whether any of them moved.
+    fn step(&mut self, rules: &VecDeque<Rule>) -> bool {
+        self.grow();
+        let proposals: Vec<Option<Pos>> = self
+            .elves
+            .iter()
+            .map(|&elf| {
+                let mut adjacent = self.adjacent(elf).peekable();
+                adjacent.peek()?;
+                rules
+                    .iter()
+                    .find(|rule| rule.passes(adjacent.clone()))
+                    .map(|rule| self.map.offset(elf, rule.move_dir.offset()).unwrap())
+            })
+            .collect();
+
+        let mut proposed: Grid<u8> = self.map.map(|_| 0);
+        for target in proposals.iter().flatten() {
+            proposed[*target] += 1;
+        }
+
+        let mut moved = false;
+        for (elf, target) in self.elves.iter_mut().zip(proposals) {
+            match target {
+                Some(target) if proposed[target] == 1 => {
+                    self.map[*elf] = false;
+                    self.map[target] = true;
+                    *elf = target;
+                    moved = true;
+                }
+                _ => {}
+            }
+        }
+        moved
+    }
+}
+
+#[allow(unused)]
+fn print(m: &Grid<bool>) {
+    println!("{}", m.map(|elf| if *elf { '#' } else { '.' }));
+}
+
+fn count_empty(elves: &[Pos]) -> usize {
+    let (minx, maxx) = elves.iter().map(|p| p.x).minmax().into_option().unwrap();
+    let (miny, maxy) = elves.iter().map(|p| p.y).minmax().into_option().unwrap();
+    (maxx - minx + 1) * (maxy - miny + 1) - elves.len()
+}
+
+// Spreads the elves until the first turn in which none of them moves. Returns the elves as
+// they were after 10 turns, and the number of that turn.
+fn spread(map: &Grid<bool>, ctx: &Context) -> Result<(Vec<Pos>, usize)> {
+    let mut rules: VecDeque<Rule> = RULES.into_iter().collect();
+    let mut elves = Elves::new(map);
+    let mut after_10 = None;
+    for turn in 1.. {
+        ctx.check()?;
+        if !elves.step(&rules) {
+            return Ok((after_10.unwrap_or(elves.elves), turn));
+        }
+        rules.rotate_left(1);
+        if turn == 10 {
+            after_10 = Some(elves.elves.clone());
+        }
+    }
+    unreachable!()
+}
+
+pub struct Input {
+    map: Grid<bool>,
+    // The elves after 10 turns and the first turn none of them moves, from whichever part runs
+    // first.
+    spread: OnceCell<(Vec<Pos>, usize)>,
+}
+
+impl Input {
+    fn spread(&self, ctx: &Context) -> Result<&(Vec<Pos>, usize)> {
+        self.spread.get_or_try_init(|| spread(&self.map, ctx))
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let map = Grid::parse(input, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(anyhow!("unexpected '{c}'")),
+        })?;
+        if !map.iter().any(|(_, elf)| *elf) {
+            bail!("no elves");
+        }
+        Ok(Input {
+            map,
+            spread: OnceCell::new(),
+        })
+    }
+
+    fn part1(input: &Self::Input, ctx: &Context) -> Result<Answer> {
+        let (after_10, _) = input.spread(ctx)?;
+        Ok(count_empty(after_10).into())
+    }
+
+    fn part2(input: &Self::Input, ctx: &Context) -> Result<Answer> {
+        let (_, last_turn) = input.spread(ctx)?;
+        Ok((*last_turn).into())
+    }
+}
@@ -0,0 +1,216 @@
+use anyhow::{anyhow, bail, Result};
+use once_cell::sync::OnceCell;
+
+use crate::dijkstra::astar;
+use crate::geom::{Dir4, Point2};
+use crate::grid::{Grid, Pos};
+use crate::solution::{Answer, Context, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Item {
+    Blizzard(Dir4),
+    Wall,
+}
+use Item::*;
+
+impl Item {
+    fn parse(c: char) -> Result<Vec<Item>> {
+        let mut ret = vec![];
+        match c {
+            '>' => ret.push(Blizzard(Dir4::Right)),
+            '<' => ret.push(Blizzard(Dir4::Left)),
+            '^' => ret.push(Blizzard(Dir4::Up)),
+            'v' => ret.push(Blizzard(Dir4::Down)),
+            '#' => ret.push(Wall),
+            '.' => {}
+            _ => bail!("unexpected '{c}'"),
+        }
+        Ok(ret)
+    }
+}
+
+// Items on every cell.
+type Map = Grid<Vec<Item>>;
+
+fn is_not_wall(m: &Map, p: Pos) -> bool {
+    m.get(p)
+        .map(|contents| contents.iter().all(|item| item != &Wall))
+        .unwrap_or(false)
+}
+
+fn is_free(m: &Map, p: Pos) -> bool {
+    m.get(p)
+        .map(|contents| contents.is_empty())
+        .unwrap_or(false)
+}
+
+// Where a blizzard at `p` blowing into the wall in `dir` comes back into the valley.
+fn wrap(p: Pos, dir: Dir4, m: &Map) -> Pos {
+    match dir {
+        Dir4::Down => Point2::new(p.x, 1),
+        Dir4::Up => Point2::new(p.x, m.height() - 2),
+        Dir4::Right => Point2::new(1, p.y),
+        Dir4::Left => Point2::new(m.width() - 2, p.y),
+    }
+}
+
+#[allow(unused)]
+fn print(m: &Map) {
+    for row in m.rows() {
+        for content in row {
+            if content.len() > 1 {
+                assert!(content.iter().all(|item| matches!(item, Blizzard(_))));
+                print!("{}", content.len());
+            } else if content.is_empty() {
+                print!(".");
+            } else {
+                match content[0] {
+                    Blizzard(Dir4::Up) => print!("^"),
+                    Blizzard(Dir4::Down) => print!("v"),
+                    Blizzard(Dir4::Left) => print!("<"),
+                    Blizzard(Dir4::Right) => print!(">"),
+                    Wall => print!("#"),
+                }
+            }
+        }
+        println!();
+    }
+}
+
+fn clean_map(m: &Map) -> Map {
+    let rows = m.height();
+    let cols = m.width();
+    let mut base = Grid::new(cols, rows, vec![]);
+    for x in 0..cols {
+        for y in [0, rows - 1] {
+            base[Point2::new(x, y)] = m[Point2::new(x, y)].clone();
+        }
+    }
+    for y in 0..rows {
+        base[Point2::new(0, y)] = vec![Wall];
+        base[Point2::new(cols - 1, y)] = vec![Wall];
+    }
+    base
+}
+
+fn next_wind(m: &Map) -> Map {
+    let mut next = clean_map(m);
+    for (pos, content) in m.iter() {
+        for dir in content.iter().flat_map(|item| {
+            if let Blizzard(d) = item {
+                Some(*d)
+            } else {
+                None
+            }
+        }) {
+            let next_wind_pos = m
+                .offset(pos, dir.offset())
+                .filter(|p| is_not_wall(&next, *p))
+                .unwrap_or_else(|| wrap(pos, dir, m));
+            next[next_wind_pos].push(Blizzard(dir));
+        }
+    }
+    next
+}
+
+// Time of arrival at `end` when leaving `start` at `start_time`.
+fn find_path(start: Pos, end: Pos, start_time: usize, states: &[Map]) -> Result<usize> {
+    // Position and time, modulo the period of the wind.
+    let neighbours_of = |&(p, t): &(Pos, usize)| {
+        let next_time = (t + 1) % states.len();
+        let next = &states[next_time];
+        // Moving or waiting in place.
+        Dir4::ALL
+            .into_iter()
+            .map(|dir| dir.offset())
+            .chain([Point2::default()])
+            .filter_map(|offset| next.offset(p, offset))
+            .filter(|next_pos| is_free(next, *next_pos))
+            .map(|next_pos| ((next_pos, next_time), 1))
+            .collect()
+    };
+    let distance = |(p, _): &(Pos, usize)| p.manhattan(end) as u64;
+    let (cost, _) = astar(
+        (start, start_time % states.len()),
+        |(p, _)| *p == end,
+        neighbours_of,
+        distance,
+    )
+    .ok_or_else(|| anyhow!("no way through the blizzards"))?;
+    Ok(start_time + cost as usize)
+}
+
+pub struct Input {
+    start_map: Map,
+    // The map at every minute until the wind repeats, from whichever part runs first.
+    states: OnceCell<Vec<Map>>,
+    start: Pos,
+    end: Pos,
+}
+
+impl Input {
+    fn states(&self, ctx: &Context) -> Result<&[Map]> {
+        let states = self
+            .states
+            .get_or_try_init(|| wind_states(&self.start_map, ctx))?;
+        Ok(states)
+    }
+}
+
+fn wind_states(start_map: &Map, ctx: &Context) -> Result<Vec<Map>> {
+    let mut states: Vec<Map> = vec![];
+    states.push(start_map.clone());
+    loop {
+        ctx.check()?;
+        let next = next_wind(states.last().unwrap());
+        if &next == start_map {
+            break;
+        }
+        states.push(next);
+    }
+    Ok(states)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let start_map: Map = Grid::parse(input, Item::parse)?;
+        if start_map.height() < 3 {
+            bail!("expected a rectangular valley with walls around it");
+        }
+
+        let start = Point2::new(1, 0);
+        if !is_free(&start_map, start) {
+            bail!("expected the entrance at the second column of the top wall");
+        }
+        let end = Point2::new(
+            start_map
+                .row(start_map.height() - 1)
+                .iter()
+                .position(|v| v.is_empty())
+                .ok_or_else(|| anyhow!("no exit in the bottom wall"))?,
+            start_map.height() - 1,
+        );
+
+        Ok(Input {
+            start_map,
+            states: OnceCell::new(),
+            start,
+            end,
+        })
+    }
+
+    fn part1(input: &Self::Input, ctx: &Context) -> Result<Answer> {
+        Ok(find_path(input.start, input.end, 0, input.states(ctx)?)?.into())
+    }
+
+    fn part2(input: &Self::Input, ctx: &Context) -> Result<Answer> {
+        let states = input.states(ctx)?;
+        let there = find_path(input.start, input.end, 0, states)?;
+        let back = find_path(input.end, input.start, there, states)?;
+        Ok(find_path(input.start, input.end, back, states)?.into())
+    }
+}
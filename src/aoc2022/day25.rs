@@ -0,0 +1,108 @@
+use anyhow::{anyhow, Result};
+
+use crate::input::try_lines;
+use crate::solution::{Answer, Context, Solution};
+
+fn c2d(c: char) -> Result<i64> {
+    match c {
+        '2' => Ok(2),
+        '1' => Ok(1),
+        '0' => Ok(0),
+        '-' => Ok(-1),
+        '=' => Ok(-2),
+        _ => Err(anyhow!("unexpected SNAFU digit '{c}'")),
+    }
+}
+
+fn d2c(i: i64) -> char {
+    match i {
+        2 => '2',
+        1 => '1',
+        0 => '0',
+        -1 => '-',
+        -2 => '=',
+        _ => todo!("{}", i),
+    }
+}
+
+fn snafu2dec(s: &str) -> Result<i64> {
+    let mut tmp = s.chars().map(c2d).collect::<Result<Vec<_>>>()?;
+    tmp.reverse();
+    Ok(snafu2dec2(&tmp))
+}
+
+fn snafu2dec2(s: &[i64]) -> i64 {
+    let mut ret = 0;
+    let mut base = 1;
+    for d in s {
+        ret += d * base;
+        base *= 5;
+    }
+    ret
+}
+
+fn dec2snafu(mut i: i64, boundries: &[(i64, i64)]) -> String {
+    let mut ret = String::new();
+    let mut c = boundries.len() - 1;
+    loop {
+        if boundries[c].0 < i {
+            break;
+        }
+        c -= 1;
+    }
+    c += 1;
+    loop {
+        let mut digit = 0;
+        if c > 0 {
+            while i > 0 && i.abs() > boundries[c - 1].0 {
+                digit += 1;
+                i -= boundries[c].1;
+            }
+            while i < 0 && i.abs() > boundries[c - 1].0 {
+                digit -= 1;
+                i += boundries[c].1;
+            }
+        } else {
+            digit = i / boundries[c].1;
+        }
+        ret.push(d2c(digit));
+        if c == 0 {
+            break;
+        }
+        c -= 1;
+    }
+    ret
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(try_lines(input, snafu2dec)?.into_iter().sum())
+    }
+
+    fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+        let mut boundries = vec![];
+        let mut base = 1;
+        let mut sum = 0;
+        for _ in 0..25 {
+            let next = sum + 2 * base;
+            boundries.push((next, base));
+            sum = next;
+            base *= 5;
+        }
+
+        assert_eq!(dec2snafu(107, &boundries), "1-12");
+        assert_eq!(dec2snafu(198, &boundries), "2=0=");
+        assert_eq!(dec2snafu(12345, &boundries), "1-0---0");
+        assert_eq!(dec2snafu(314159265, &boundries), "1121-1110-1=0");
+
+        Ok(dec2snafu(*input, &boundries).into())
+    }
+
+    fn part2(_: &Self::Input, _: &Context) -> Result<Answer> {
+        Ok(Answer::Empty)
+    }
+}
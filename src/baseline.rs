@@ -0,0 +1,198 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Error, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::answers::input_name;
+use crate::report::{Report, Stats};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayStats {
+    pub day: usize,
+    // Input name, so that runs from different checkouts or input dirs compare.
+    pub input: String,
+    pub samples: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+}
+
+// Per-day compute time statistics of a run, stored as json under a name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub days: Vec<DayStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    pub day: usize,
+    pub input: String,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(
+            f,
+            "Day {:02} ({}) median {:.1?} -> {:.1?} ({:+.1}%)",
+            self.day,
+            self.input,
+            self.baseline,
+            self.current,
+            change(self.baseline, self.current) * 100.0
+        )
+    }
+}
+
+fn change(before: Duration, after: Duration) -> f64 {
+    after.as_secs_f64() / before.as_secs_f64() - 1.0
+}
+
+impl Baseline {
+    pub fn from_report(report: &Report) -> Self {
+        let days = report
+            .days
+            .iter()
+            .flat_map(|d| {
+                let samples: Vec<Duration> = d.samples.iter().map(|s| s.compute).collect();
+                Stats::new(&samples).map(|t| DayStats {
+                    day: d.day,
+                    input: input_name(Path::new(&d.input)),
+                    samples: samples.len(),
+                    min_ns: t.min.as_nanos() as u64,
+                    median_ns: t.median.as_nanos() as u64,
+                })
+            })
+            .collect();
+        Self { days }
+    }
+
+    pub fn path(dir: &Path, name: &str) -> PathBuf {
+        dir.join(format!("{name}.json"))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let s = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read '{}'", path.display()))?;
+        serde_json::from_str(&s).with_context(|| format!("failed to parse '{}'", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create '{}'", dir.display()))?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write '{}'", path.display()))
+    }
+
+    // Replaces stats of days present in `other`, so that runs of a subset of days update
+    // only their part of a saved baseline.
+    pub fn merge(&mut self, other: Baseline) {
+        for stats in other.days {
+            self.days
+                .retain(|d| (d.day, &d.input) != (stats.day, &stats.input));
+            self.days.push(stats);
+        }
+        self.days
+            .sort_by(|a, b| (a.day, &a.input).cmp(&(b.day, &b.input)));
+    }
+
+    pub fn get(&self, day: usize, input: &str) -> Option<&DayStats> {
+        self.days.iter().find(|d| d.day == day && d.input == input)
+    }
+
+    // Days of `current` whose median is more than `threshold` (0.1 is 10%) slower than here.
+    // Days missing from this baseline are not compared.
+    pub fn regressions(&self, current: &Baseline, threshold: f64) -> Vec<Regression> {
+        current
+            .days
+            .iter()
+            .flat_map(|d| {
+                let before = self.get(d.day, &d.input)?;
+                let baseline = Duration::from_nanos(before.median_ns);
+                let current = Duration::from_nanos(d.median_ns);
+                (change(baseline, current) > threshold).then(|| Regression {
+                    day: d.day,
+                    input: d.input.clone(),
+                    baseline,
+                    current,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(day: usize, median_ns: u64) -> DayStats {
+        DayStats {
+            day,
+            input: format!("day{day:02}"),
+            samples: 1,
+            min_ns: median_ns,
+            median_ns,
+        }
+    }
+
+    #[test]
+    fn regressions_test() {
+        let before = Baseline {
+            days: vec![stats(1, 100), stats(2, 100), stats(3, 100)],
+        };
+        let after = Baseline {
+            days: vec![stats(1, 109), stats(2, 111), stats(3, 50), stats(4, 1000)],
+        };
+        assert_eq!(
+            vec![Regression {
+                day: 2,
+                input: "day02".to_owned(),
+                baseline: Duration::from_nanos(100),
+                current: Duration::from_nanos(111),
+            }],
+            before.regressions(&after, 0.1)
+        );
+        assert_eq!(
+            "Day 02 (day02) median 100.0ns -> 111.0ns (+11.0%)",
+            before.regressions(&after, 0.1)[0].to_string()
+        );
+        assert_eq!(
+            vec![3],
+            after
+                .regressions(&before, 0.0)
+                .iter()
+                .map(|r| r.day)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn save_load_test() {
+        let path = Baseline::path(&std::env::temp_dir().join("aoc22-baseline-test"), "main");
+        let baseline = Baseline {
+            days: vec![stats(1, 100), stats(16, 69_800_000_000)],
+        };
+        baseline.save(&path).unwrap();
+        assert_eq!(baseline, Baseline::load(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn merge_test() {
+        let mut baseline = Baseline {
+            days: vec![stats(1, 100), stats(3, 100)],
+        };
+        baseline.merge(Baseline {
+            days: vec![stats(3, 200), stats(2, 50)],
+        });
+        assert_eq!(
+            Baseline {
+                days: vec![stats(1, 100), stats(2, 50), stats(3, 200)]
+            },
+            baseline
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::cmp::{Ordering, Reverse};
 use std::collections::{BinaryHeap, VecDeque};
 use std::fmt::Debug;
@@ -21,7 +21,8 @@ pub fn path<T: PartialEq + Eq + Hash + Clone>(
     Some(path)
 }
 
-// Returns cost of path from start and previous nodes for path reconstruction.
+// Returns previous nodes for path reconstruction, stopping at the first node reached that is a
+// target.
 pub fn bfs<T>(
     start: T,
     is_target: impl Fn(&T) -> bool,
@@ -51,11 +52,117 @@ where
     prev
 }
 
-// Returns cost of path from start and previous nodes for path reconstruction.
-pub fn dijkstra<T>(
+// Iterator over the nodes reached by a breadth first search from any of the starts, a Vec per
+// number of steps, starting with the starts themselves. A layer is expanded into the next one
+// as it's handed out, unless it's the last one `max_depth` allows.
+pub struct BfsLayers<T, F> {
+    seen: FxHashSet<T>,
+    // The next layer to hand out.
+    layer: Vec<T>,
+    // Steps from the starts to the nodes of `layer`.
+    depth: u64,
+    max_depth: Option<u64>,
+    neighbours_of: F,
+}
+
+impl<T, F> BfsLayers<T, F> {
+    // Stops after the layer `max_depth` steps away from the starts, without expanding it.
+    pub fn max_depth(self, max_depth: Option<u64>) -> Self {
+        Self { max_depth, ..self }
+    }
+}
+
+impl<T, I, F> Iterator for BfsLayers<T, F>
+where
+    T: Eq + Hash + Clone,
+    I: IntoIterator<Item = T>,
+    F: Fn(&T) -> I,
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.layer.is_empty() {
+            return None;
+        }
+        let mut next = vec![];
+        if self.max_depth != Some(self.depth) {
+            for node in &self.layer {
+                for candidate in (self.neighbours_of)(node) {
+                    if self.seen.insert(candidate.clone()) {
+                        next.push(candidate);
+                    }
+                }
+            }
+        }
+        self.depth += 1;
+        Some(std::mem::replace(&mut self.layer, next))
+    }
+}
+
+pub fn bfs_layers<T, I, F>(starts: impl IntoIterator<Item = T>, neighbours_of: F) -> BfsLayers<T, F>
+where
+    T: Eq + Hash + Clone,
+    I: IntoIterator<Item = T>,
+    F: Fn(&T) -> I,
+{
+    let mut seen: FxHashSet<T> = Default::default();
+    let layer = starts
+        .into_iter()
+        .filter(|start| seen.insert(start.clone()))
+        .collect();
+    BfsLayers {
+        seen,
+        layer,
+        depth: 0,
+        max_depth: None,
+        neighbours_of,
+    }
+}
+
+// Returns the number of steps from the nearest start to every node reached, up to `max_depth`
+// steps if given.
+pub fn bfs_distances<T, I>(
+    starts: impl IntoIterator<Item = T>,
+    max_depth: Option<u64>,
+    neighbours_of: impl Fn(&T) -> I,
+) -> FxHashMap<T, u64>
+where
+    T: Eq + Hash + Clone,
+    I: IntoIterator<Item = T>,
+{
+    bfs_layers(starts, neighbours_of)
+        .max_depth(max_depth)
+        .zip(0..)
+        .flat_map(|(layer, depth)| layer.into_iter().map(move |node| (node, depth)))
+        .collect()
+}
+
+// Heap entry of dijkstra and astar, ordered by priority first.
+#[derive(Debug, PartialEq, Eq, Ord)]
+struct State<U: Debug + PartialEq + Eq + PartialOrd + Ord> {
+    key: U,
+    prio: u64,
+}
+
+impl<U: Debug + PartialEq + Eq + PartialOrd + Ord> PartialOrd for State<U> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        let o = self.prio.partial_cmp(&other.prio);
+        if o == Some(Ordering::Equal) {
+            self.key.partial_cmp(&other.key)
+        } else {
+            o
+        }
+    }
+}
+
+// Settles nodes in order of their cost from start until `done` holds for one of them, which is
+// returned together with the costs of all nodes reached and previous nodes for path
+// reconstruction.
+fn settle<T>(
     start: T,
     neighbours_of: impl Fn(&T) -> Vec<(T, u64)>,
-) -> (FxHashMap<T, u64>, FxHashMap<T, T>)
+    mut done: impl FnMut(&T) -> bool,
+) -> (Option<T>, FxHashMap<T, u64>, FxHashMap<T, T>)
 where
     T: Debug + PartialEq + Eq + PartialOrd + Ord + Hash + Clone,
 {
@@ -64,45 +171,388 @@ where
 
     let mut prev: FxHashMap<T, T> = Default::default();
 
-    #[derive(Debug, PartialEq, Eq, Ord)]
-    struct State<U: Debug + PartialEq + Eq + PartialOrd + Ord> {
-        key: U,
-        prio: u64,
-    }
+    let mut todo: BinaryHeap<Reverse<State<T>>> = BinaryHeap::default();
+    todo.push(Reverse(State {
+        key: start,
+        prio: 0,
+    }));
 
-    impl<U: Debug + PartialEq + Eq + PartialOrd + Ord> PartialOrd for State<U> {
-        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-            let o = self.prio.partial_cmp(&other.prio);
-            if o == Some(Ordering::Equal) {
-                self.key.partial_cmp(&other.key)
-            } else {
-                o
+    while let Some(Reverse(State { key, prio })) = todo.pop() {
+        // The heap has no decrease-key, so a node is pushed again whenever a cheaper path to it
+        // is found. The entries pushed before it are stale and skipped.
+        if prio > dist[&key] {
+            continue;
+        }
+        if done(&key) {
+            return (Some(key), dist, prev);
+        }
+        for (neighbour, cost) in neighbours_of(&key) {
+            let alt = prio + cost;
+            if alt < *dist.get(&neighbour).unwrap_or(&u64::MAX) {
+                dist.insert(neighbour.clone(), alt);
+                prev.insert(neighbour.clone(), key.clone());
+                todo.push(Reverse(State {
+                    key: neighbour,
+                    prio: alt,
+                }));
             }
         }
     }
+    (None, dist, prev)
+}
+
+// Returns cost of path from start and previous nodes for path reconstruction.
+pub fn dijkstra<T>(
+    start: T,
+    neighbours_of: impl Fn(&T) -> Vec<(T, u64)>,
+) -> (FxHashMap<T, u64>, FxHashMap<T, T>)
+where
+    T: Debug + PartialEq + Eq + PartialOrd + Ord + Hash + Clone,
+{
+    let (_, dist, prev) = settle(start, neighbours_of, |_| false);
+    (dist, prev)
+}
+
+// Returns the nearest node that is a target with its cost, and previous nodes for path
+// reconstruction. Stops as soon as it's settled, so nodes further away are never expanded.
+pub fn dijkstra_until<T>(
+    start: T,
+    is_target: impl Fn(&T) -> bool,
+    neighbours_of: impl Fn(&T) -> Vec<(T, u64)>,
+) -> Option<(T, u64, FxHashMap<T, T>)>
+where
+    T: Debug + PartialEq + Eq + PartialOrd + Ord + Hash + Clone,
+{
+    let (target, dist, prev) = settle(start, neighbours_of, is_target);
+    let target = target?;
+    let cost = dist[&target];
+    Some((target, cost, prev))
+}
+
+// Returns costs of the targets reachable from start and previous nodes for path
+// reconstruction. Stops once all targets are settled.
+pub fn dijkstra_targets<T>(
+    start: T,
+    targets: impl IntoIterator<Item = T>,
+    neighbours_of: impl Fn(&T) -> Vec<(T, u64)>,
+) -> (FxHashMap<T, u64>, FxHashMap<T, T>)
+where
+    T: Debug + PartialEq + Eq + PartialOrd + Ord + Hash + Clone,
+{
+    let targets: FxHashSet<T> = targets.into_iter().collect();
+    let mut left = targets.clone();
+    let (_, dist, prev) = settle(start, neighbours_of, |n| {
+        left.remove(n);
+        left.is_empty()
+    });
+    let costs = targets
+        .into_iter()
+        .filter_map(|t| dist.get(&t).map(|cost| (t, *cost)))
+        .collect();
+    (costs, prev)
+}
+
+// Returns cost and path from start to the first node reached that is a target, if any. The
+// heuristic estimates the cost from a node to the nearest target and must never overestimate
+// it, otherwise the path found may not be the cheapest.
+pub fn astar<T>(
+    start: T,
+    is_target: impl Fn(&T) -> bool,
+    neighbours_of: impl Fn(&T) -> Vec<(T, u64)>,
+    heuristic: impl Fn(&T) -> u64,
+) -> Option<(u64, Vec<T>)>
+where
+    T: Debug + PartialEq + Eq + PartialOrd + Ord + Hash + Clone,
+{
+    let mut dist: FxHashMap<T, u64> = Default::default();
+    dist.insert(start.clone(), 0);
+
+    let mut prev: FxHashMap<T, T> = Default::default();
+
     let mut todo: BinaryHeap<Reverse<State<T>>> = BinaryHeap::default();
     todo.push(Reverse(State {
+        prio: heuristic(&start),
         key: start.clone(),
-        prio: *dist.get(&start).unwrap(),
     }));
 
     while let Some(Reverse(State { key, prio })) = todo.pop() {
-        for (neighbour, cost) in neighbours_of(&key) {
-            debug_assert_eq!(&prio, dist.get(&key).unwrap());
-            let alt = prio + cost;
+        let cost = dist[&key];
+        // Stale entry, as in dijkstra.
+        if prio > cost + heuristic(&key) {
+            continue;
+        }
+        if is_target(&key) {
+            return Some((cost, path(&start, &key, &prev)?));
+        }
+        for (neighbour, step) in neighbours_of(&key) {
+            let alt = cost + step;
             if alt < *dist.get(&neighbour).unwrap_or(&u64::MAX) {
                 dist.insert(neighbour.clone(), alt);
                 prev.insert(neighbour.clone(), key.clone());
                 todo.push(Reverse(State {
+                    prio: alt + heuristic(&neighbour),
                     key: neighbour,
-                    prio: alt,
                 }));
             }
         }
     }
+    None
+}
+
+// Numbers the nodes of a graph from 0 to `node_count() - 1`, so that searches can keep their
+// state in Vecs instead of hash maps.
+pub trait Indexer<T> {
+    fn node_count(&self) -> usize;
+    fn index(&self, node: &T) -> usize;
+    fn node(&self, index: usize) -> T;
+}
+
+// Priority queue for small integer priorities, none lower than the last one popped. Keeps a
+// bucket per priority from that one up to the highest pushed, so the highest step cost has to
+// be small.
+#[derive(Default)]
+struct BucketQueue {
+    base: u64,
+    len: usize,
+    buckets: VecDeque<Vec<usize>>,
+}
+
+impl BucketQueue {
+    fn push(&mut self, prio: u64, index: usize) {
+        debug_assert!(prio >= self.base);
+        let i = (prio - self.base) as usize;
+        if i >= self.buckets.len() {
+            self.buckets.resize_with(i + 1, Vec::new);
+        }
+        self.buckets[i].push(index);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(u64, usize)> {
+        if self.len == 0 {
+            return None;
+        }
+        loop {
+            if let Some(index) = self.buckets[0].pop() {
+                self.len -= 1;
+                return Some((self.base, index));
+            }
+            // Reuse the empty bucket for the next priority past the last one.
+            self.buckets.rotate_left(1);
+            self.base += 1;
+        }
+    }
+}
+
+// Like `path`, for the predecessors returned by the dense searches.
+pub fn path_dense<T>(
+    from: &T,
+    to: &T,
+    prev: &[Option<usize>],
+    indexer: &impl Indexer<T>,
+) -> Option<Vec<T>> {
+    let from = indexer.index(from);
+    let mut i = indexer.index(to);
+    let mut path = vec![i];
+    while i != from {
+        i = prev[i]?;
+        path.push(i);
+    }
+    Some(path.into_iter().rev().map(|i| indexer.node(i)).collect())
+}
+
+// Like `bfs`, for nodes numbered by `indexer`. Returns the number of steps from start to every
+// node, u64::MAX for those not reached, and the index of the previous node on the way.
+pub fn bfs_dense<T, I>(
+    start: T,
+    indexer: &impl Indexer<T>,
+    is_target: impl Fn(&T) -> bool,
+    neighbours_of: impl Fn(&T) -> I,
+) -> (Vec<u64>, Vec<Option<usize>>)
+where
+    I: IntoIterator<Item = T>,
+{
+    let mut dist = vec![u64::MAX; indexer.node_count()];
+    let mut prev = vec![None; indexer.node_count()];
+    let mut todo: VecDeque<usize> = Default::default();
+    let start = indexer.index(&start);
+    dist[start] = 0;
+    todo.push_back(start);
+
+    while let Some(i) = todo.pop_front() {
+        let node = indexer.node(i);
+        if is_target(&node) {
+            break;
+        }
+        for candidate in neighbours_of(&node) {
+            let j = indexer.index(&candidate);
+            if dist[j] != u64::MAX {
+                continue;
+            }
+            dist[j] = dist[i] + 1;
+            prev[j] = Some(i);
+            todo.push_back(j);
+        }
+    }
+    (dist, prev)
+}
+
+// Like `dijkstra`, for nodes numbered by `indexer` and small step costs. Returns the cost of
+// the path from start to every node, u64::MAX for those not reached, and the index of the
+// previous node on it.
+pub fn dijkstra_dense<T, I>(
+    start: T,
+    indexer: &impl Indexer<T>,
+    neighbours_of: impl Fn(&T) -> I,
+) -> (Vec<u64>, Vec<Option<usize>>)
+where
+    I: IntoIterator<Item = (T, u64)>,
+{
+    let mut dist = vec![u64::MAX; indexer.node_count()];
+    let mut prev = vec![None; indexer.node_count()];
+    let mut todo = BucketQueue::default();
+    let start = indexer.index(&start);
+    dist[start] = 0;
+    todo.push(0, start);
+
+    while let Some((prio, i)) = todo.pop() {
+        // Stale entry, as in dijkstra.
+        if prio > dist[i] {
+            continue;
+        }
+        for (neighbour, cost) in neighbours_of(&indexer.node(i)) {
+            let j = indexer.index(&neighbour);
+            let alt = prio + cost;
+            if alt < dist[j] {
+                dist[j] = alt;
+                prev[j] = Some(i);
+                todo.push(alt, j);
+            }
+        }
+    }
     (dist, prev)
 }
 
+// Shortest distances between all pairs of nodes of a small graph, numbered from 0, with the
+// first step from every node towards every other for path reconstruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllPairs {
+    dist: Vec<Vec<u64>>,
+    next: Vec<Vec<Option<usize>>>,
+}
+
+// The nodes of a graph worth visiting and the distances between them, through all the others.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compressed {
+    pub nodes: Vec<usize>,
+    dist: Vec<Vec<u64>>,
+}
+
+impl AllPairs {
+    pub fn node_count(&self) -> usize {
+        self.dist.len()
+    }
+
+    pub fn dist(&self, from: usize, to: usize) -> Option<u64> {
+        let dist = self.dist[from][to];
+        (dist != u64::MAX).then_some(dist)
+    }
+
+    // Nodes on a shortest path from `from` to `to`, both included.
+    pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let mut path = vec![from];
+        let mut node = from;
+        while node != to {
+            node = self.next[node][to]?;
+            path.push(node);
+        }
+        Some(path)
+    }
+
+    // Drops the nodes for which `keep` doesn't hold, like valves that aren't worth opening.
+    pub fn compress(&self, keep: impl Fn(usize) -> bool) -> Compressed {
+        let nodes: Vec<usize> = (0..self.node_count()).filter(|n| keep(*n)).collect();
+        let dist = nodes
+            .iter()
+            .map(|from| nodes.iter().map(|to| self.dist[*from][*to]).collect())
+            .collect();
+        Compressed { nodes, dist }
+    }
+}
+
+impl Compressed {
+    // Between `nodes[from]` and `nodes[to]`.
+    pub fn dist(&self, from: usize, to: usize) -> Option<u64> {
+        let dist = self.dist[from][to];
+        (dist != u64::MAX).then_some(dist)
+    }
+}
+
+// All pairs shortest paths of a graph given as lists of edges with their costs, by
+// Floyd-Warshall.
+pub fn floyd_warshall(adjacency: &[Vec<(usize, u64)>]) -> AllPairs {
+    let n = adjacency.len();
+    let mut dist = vec![vec![u64::MAX; n]; n];
+    let mut next = vec![vec![None; n]; n];
+    for (from, edges) in adjacency.iter().enumerate() {
+        dist[from][from] = 0;
+        next[from][from] = Some(from);
+        for &(to, cost) in edges {
+            if cost < dist[from][to] {
+                dist[from][to] = cost;
+                next[from][to] = Some(to);
+            }
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            if dist[i][k] == u64::MAX {
+                continue;
+            }
+            for j in 0..n {
+                let alt = dist[i][k].saturating_add(dist[k][j]);
+                if alt < dist[i][j] {
+                    dist[i][j] = alt;
+                    next[i][j] = next[i][k];
+                }
+            }
+        }
+    }
+    AllPairs { dist, next }
+}
+
+// All pairs shortest paths of a graph given as lists of neighbours, every step costing 1, by a
+// breadth first search from every node.
+pub fn all_pairs_bfs(adjacency: &[Vec<usize>]) -> AllPairs {
+    let n = adjacency.len();
+    let mut dist = vec![vec![u64::MAX; n]; n];
+    let mut next = vec![vec![None; n]; n];
+    let mut todo: VecDeque<usize> = VecDeque::new();
+    for from in 0..n {
+        // The first step from `from` towards every node.
+        let first = &mut next[from];
+        let dist = &mut dist[from];
+        dist[from] = 0;
+        first[from] = Some(from);
+        todo.push_back(from);
+        while let Some(node) = todo.pop_front() {
+            for &neighbour in &adjacency[node] {
+                if dist[neighbour] != u64::MAX {
+                    continue;
+                }
+                dist[neighbour] = dist[node] + 1;
+                first[neighbour] = if node == from {
+                    Some(neighbour)
+                } else {
+                    first[node]
+                };
+                todo.push_back(neighbour);
+            }
+        }
+    }
+    AllPairs { dist, next }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,6 +578,22 @@ mod tests {
     use Node::*;
     const ALL: [Node; 12] = [A, B, C, D, E, F, G, H, I, J, K, L];
 
+    struct Nodes;
+
+    impl Indexer<Node> for Nodes {
+        fn node_count(&self) -> usize {
+            ALL.len()
+        }
+
+        fn index(&self, node: &Node) -> usize {
+            *node as usize
+        }
+
+        fn node(&self, index: usize) -> Node {
+            ALL[index]
+        }
+    }
+
     #[test]
     fn full_graph() {
         let start = A;
@@ -182,6 +648,37 @@ mod tests {
         assert_eq!(d_path, b_path);
     }
 
+    #[test]
+    fn compress_test() {
+        // A - B - C - D, with B and C not worth visiting.
+        let adjacency = vec![vec![1], vec![0, 2], vec![1, 3], vec![2]];
+        let all_pairs = all_pairs_bfs(&adjacency);
+        assert_eq!(Some(vec![3, 2, 1, 0]), all_pairs.path(3, 0));
+        assert_eq!(
+            Compressed {
+                nodes: vec![0, 3],
+                dist: vec![vec![0, 3], vec![3, 0]],
+            },
+            all_pairs.compress(|n| n == 0 || n == 3)
+        );
+        let one_way = all_pairs_bfs(&[vec![1], vec![]]);
+        assert_eq!(None, one_way.dist(1, 0));
+        assert_eq!(None, one_way.compress(|_| true).dist(1, 0));
+        assert_eq!(Some(1), one_way.compress(|_| true).dist(0, 1));
+    }
+
+    #[test]
+    fn bfs_max_depth_test() {
+        // An endless chain 0 - 1 - 2 - ..., of which only the nodes up to 2 may be expanded.
+        let expanded = std::cell::RefCell::new(vec![]);
+        let dist = bfs_distances([0], Some(2), |&n: &u64| {
+            expanded.borrow_mut().push(n);
+            [n + 1]
+        });
+        assert_eq!(FxHashMap::from_iter([(0, 0), (1, 1), (2, 2)]), dist);
+        assert_eq!(vec![0, 1], expanded.into_inner());
+    }
+
     proptest! {
         #[test]
         fn bfs_and_dijkstra_equal(nodes: HashMap<Node, HashSet<Node>>, start: Node) {
@@ -201,5 +698,188 @@ mod tests {
             }
 
         }
+
+        #[test]
+        fn astar_and_dijkstra_equal(
+            edges: HashMap<Node, HashMap<Node, u8>>,
+            start: Node,
+            target: Node,
+            percent in 0..=100u64,
+        ) {
+            let n_of = |n: &Node| {
+                edges
+                    .get(n)
+                    .into_iter()
+                    .flatten()
+                    .map(|(n, cost)| (*n, *cost as u64))
+                    .collect::<Vec<_>>()
+            };
+            // A fraction of the real cost to the target never overestimates it.
+            let to_target: HashMap<Node, u64> = ALL
+                .into_iter()
+                .flat_map(|n| dijkstra(n, n_of).0.get(&target).map(|d| (n, d * percent / 100)))
+                .collect();
+            let heuristic = |n: &Node| to_target.get(n).copied().unwrap_or(0);
+
+            let (d_dist, _) = dijkstra(start, n_of);
+            let found = astar(start, |n| n == &target, n_of, heuristic);
+            prop_assert_eq!(d_dist.get(&target).copied(), found.as_ref().map(|(cost, _)| *cost));
+            if let Some((cost, path)) = found {
+                prop_assert_eq!(Some(&start), path.first());
+                prop_assert_eq!(Some(&target), path.last());
+                let path_cost: u64 = path
+                    .windows(2)
+                    .map(|w| edges[&w[0]][&w[1]] as u64)
+                    .sum();
+                prop_assert_eq!(cost, path_cost);
+            }
+        }
+
+        #[test]
+        fn early_exit_and_dijkstra_equal(
+            edges: HashMap<Node, HashMap<Node, u8>>,
+            start: Node,
+            targets: HashSet<Node>,
+        ) {
+            let n_of = |n: &Node| {
+                edges
+                    .get(n)
+                    .into_iter()
+                    .flatten()
+                    .map(|(n, cost)| (*n, *cost as u64))
+                    .collect::<Vec<_>>()
+            };
+            let (d_dist, _) = dijkstra(start, n_of);
+            let nearest = targets.iter().filter_map(|t| d_dist.get(t)).min().copied();
+
+            let found = dijkstra_until(start, |n| targets.contains(n), n_of);
+            prop_assert_eq!(nearest, found.as_ref().map(|(_, cost, _)| *cost));
+            if let Some((target, cost, prev)) = found {
+                prop_assert!(targets.contains(&target));
+                let path = path(&start, &target, &prev).unwrap();
+                let path_cost: u64 = path
+                    .windows(2)
+                    .map(|w| edges[&w[0]][&w[1]] as u64)
+                    .sum();
+                prop_assert_eq!(cost, path_cost);
+            }
+
+            let (costs, _) = dijkstra_targets(start, targets.iter().copied(), n_of);
+            let expected: FxHashMap<Node, u64> = targets
+                .iter()
+                .filter_map(|t| d_dist.get(t).map(|d| (*t, *d)))
+                .collect();
+            prop_assert_eq!(expected, costs);
+        }
+
+        #[test]
+        fn dense_and_sparse_equal(
+            edges: HashMap<Node, HashMap<Node, u8>>,
+            start: Node,
+            target: Node,
+        ) {
+            let n_of = |n: &Node| {
+                edges
+                    .get(n)
+                    .into_iter()
+                    .flatten()
+                    .map(|(n, cost)| (*n, *cost as u64))
+                    .collect::<Vec<_>>()
+            };
+            let (d_dist, _) = dijkstra(start, n_of);
+            let (dense_dist, dense_prev) = dijkstra_dense(start, &Nodes, n_of);
+            for n in ALL {
+                prop_assert_eq!(d_dist.get(&n).copied().unwrap_or(u64::MAX), dense_dist[n as usize]);
+            }
+            if let Some(path) = path_dense(&start, &target, &dense_prev, &Nodes) {
+                let path_cost: u64 = path
+                    .windows(2)
+                    .map(|w| edges[&w[0]][&w[1]] as u64)
+                    .sum();
+                prop_assert_eq!(dense_dist[target as usize], path_cost);
+            }
+
+            let b_of = |n: &Node| edges.get(n).into_iter().flat_map(|e| e.keys().copied());
+            let b_prev = bfs(start, |n| n == &target, |n| b_of(n).collect::<Vec<_>>());
+            let (_, dense_prev) = bfs_dense(start, &Nodes, |n| n == &target, b_of);
+            prop_assert_eq!(
+                path(&start, &target, &b_prev).map(|p| p.len()),
+                path_dense(&start, &target, &dense_prev, &Nodes).map(|p| p.len())
+            );
+        }
+
+        #[test]
+        fn all_pairs_and_dijkstra_equal(edges: HashMap<Node, HashMap<Node, u8>>) {
+            let n_of = |n: &Node| {
+                edges
+                    .get(n)
+                    .into_iter()
+                    .flatten()
+                    .map(|(n, cost)| (*n, *cost as u64))
+                    .collect::<Vec<_>>()
+            };
+            let adjacency: Vec<Vec<(usize, u64)>> = ALL
+                .into_iter()
+                .map(|n| n_of(&n).into_iter().map(|(n, cost)| (n as usize, cost)).collect())
+                .collect();
+            let weighted = floyd_warshall(&adjacency);
+            let unweighted: Vec<Vec<usize>> = adjacency
+                .iter()
+                .map(|edges| edges.iter().map(|(n, _)| *n).collect())
+                .collect();
+            let steps = all_pairs_bfs(&unweighted);
+            let ones: Vec<Vec<(usize, u64)>> = unweighted
+                .iter()
+                .map(|edges| edges.iter().map(|n| (*n, 1)).collect())
+                .collect();
+            prop_assert_eq!(floyd_warshall(&ones).dist, steps.dist.clone());
+
+            for from in ALL {
+                let (d_dist, _) = dijkstra(from, n_of);
+                for to in ALL {
+                    let dist = weighted.dist(from as usize, to as usize);
+                    prop_assert_eq!(d_dist.get(&to).copied(), dist);
+                    let path = weighted.path(from as usize, to as usize);
+                    prop_assert_eq!(dist.is_some(), path.is_some());
+                    if let Some(path) = path {
+                        let path_cost: u64 = path
+                            .windows(2)
+                            .map(|w| edges[&ALL[w[0]]][&ALL[w[1]]] as u64)
+                            .sum();
+                        prop_assert_eq!(dist, Some(path_cost));
+                    }
+                    if let Some(path) = steps.path(from as usize, to as usize) {
+                        prop_assert_eq!(steps.dist(from as usize, to as usize), Some(path.len() as u64 - 1));
+                        prop_assert!(path.windows(2).all(|w| unweighted[w[0]].contains(&w[1])));
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn bfs_distances_and_dijkstra_equal(
+            nodes: HashMap<Node, HashSet<Node>>,
+            starts: HashSet<Node>,
+            max_depth in proptest::option::of(0..4u64),
+        ) {
+            let n_of = |n: &Node| nodes.get(n).into_iter().flatten().copied();
+            // Distances from the nearest start are the shortest of the ones from every start.
+            let mut expected: FxHashMap<Node, u64> = Default::default();
+            for start in &starts {
+                let (dist, _) = dijkstra(*start, |n| n_of(n).map(|n| (n, 1)).collect());
+                for (n, d) in dist {
+                    if d <= max_depth.unwrap_or(u64::MAX) {
+                        let e = expected.entry(n).or_insert(d);
+                        *e = (*e).min(d);
+                    }
+                }
+            }
+            prop_assert_eq!(&expected, &bfs_distances(starts.iter().copied(), max_depth, n_of));
+
+            let layers = bfs_layers(starts.iter().copied(), n_of).take(max_depth.map_or(usize::MAX, |d| d as usize + 1));
+            for (depth, layer) in layers.enumerate() {
+                prop_assert!(layer.iter().all(|n| expected.get(n) == Some(&(depth as u64))));
+            }
+        }
     }
 }
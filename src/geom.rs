@@ -0,0 +1,285 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+// Point or offset on a plane. Like on the character maps of the puzzles, y grows downwards, so
+// `Dir4::Up` is a step to a smaller y.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+// |a - b|, also for unsigned types.
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point2<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+impl<T: Copy + Add<Output = T> + From<i8>> Point2<T> {
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Dir4::ALL.into_iter().map(move |d| self + d.offset())
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Dir8::ALL.into_iter().map(move |d| self + d.offset())
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x)
+            .max(abs_diff(self.y, other.y))
+            .max(abs_diff(self.z, other.z))
+    }
+}
+
+impl<T: Copy + Add<Output = T> + From<i8>> Point3<T> {
+    // The 6 points sharing a face with this one, like cubes of a lava droplet.
+    pub fn neighbours6(self) -> impl Iterator<Item = Self> {
+        [
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ]
+        .into_iter()
+        .map(move |(x, y, z)| self + Point3::new(T::from(x), T::from(y), T::from(z)))
+    }
+}
+
+// Component-wise operators, with multiplication by a scalar.
+macro_rules! impl_ops {
+    ($point:ident { $($c:ident),+ }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($c: self.$c + other.$c),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($c: self.$c - other.$c),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($c: -self.$c),+ }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, n: T) -> Self {
+                Self { $($c: self.$c * n),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                $(self.$c += other.$c;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                $(self.$c -= other.$c;)+
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    // Clockwise from up.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    // Turned 90 degrees counterclockwise.
+    pub fn left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    // Turned 90 degrees clockwise.
+    pub fn right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    // A step in this direction.
+    pub fn offset<T: From<i8>>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Self::Up => (0, -1),
+            Self::Right => (1, 0),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+        };
+        Point2::new(T::from(x), T::from(y))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    // Clockwise from north.
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    // Turned 45 degrees counterclockwise.
+    pub fn left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    // Turned 45 degrees clockwise.
+    pub fn right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    // A step in this direction, north is up.
+    pub fn offset<T: From<i8>>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Self::N => (0, -1),
+            Self::NE => (1, -1),
+            Self::E => (1, 0),
+            Self::SE => (1, 1),
+            Self::S => (0, 1),
+            Self::SW => (-1, 1),
+            Self::W => (-1, 0),
+            Self::NW => (-1, -1),
+        };
+        Point2::new(T::from(x), T::from(y))
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(d: Dir4) -> Self {
+        Self::ALL[d as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_test() {
+        let a = Point2::new(1, 5);
+        let b = Point2::new(4, -1);
+        assert_eq!(Point2::new(5, 4), a + b);
+        assert_eq!(Point2::new(-3, 6), a - b);
+        assert_eq!(Point2::new(-2, -10), -a * 2);
+        assert_eq!(9, a.manhattan(b));
+        assert_eq!(6, a.chebyshev(b));
+        assert_eq!(3, Point2::new(2u8, 0).manhattan(Point2::new(1, 2)));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(b, c);
+        assert_eq!(4, Point2::<i32>::default().neighbours4().count());
+        assert!(Point2::new(0i64, 0)
+            .neighbours8()
+            .all(|n| n.chebyshev(Point2::default()) == 1));
+
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(Point3::new(2, 4, 6), p + p);
+        assert_eq!(6, p.manhattan(Point3::default()));
+        assert_eq!(3, p.chebyshev(Point3::default()));
+        assert!(p.neighbours6().all(|n| n.manhattan(p) == 1));
+    }
+
+    #[test]
+    fn dir_test() {
+        assert_eq!(Dir4::Left, Dir4::Up.left());
+        assert_eq!(Dir4::Up, Dir4::Left.right());
+        assert_eq!(Dir4::Down, Dir4::Up.opposite());
+        assert_eq!(Point2::new(0, -1), Dir4::Up.offset::<i32>());
+        assert_eq!(
+            Point2::<i16>::default(),
+            Dir4::Right.offset() + Dir4::Right.opposite().offset()
+        );
+        assert_eq!(Dir8::NW, Dir8::N.left());
+        assert_eq!(Dir8::SW, Dir8::NE.opposite());
+        assert_eq!(Dir8::W, Dir4::Left.into());
+        assert!(Dir8::ALL
+            .into_iter()
+            .all(|d| d.offset::<i32>() + d.opposite().offset() == Point2::default()));
+    }
+}
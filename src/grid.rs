@@ -0,0 +1,326 @@
+use anyhow::{anyhow, bail, Result};
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use crate::dijkstra::Indexer;
+use crate::geom::{Dir4, Dir8, Point2};
+
+// Column (x) and row (y) of a cell, counted from the top left corner.
+pub type Pos = Point2<usize>;
+
+// Rectangular grid stored row after row in a single Vec.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some(i) = rows.iter().position(|row| row.len() != width) {
+            bail!(
+                "row {} has {} cells, expected {width}",
+                i + 1,
+                rows[i].len()
+            );
+        }
+        Ok(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    // Parses a character map, a line per row, with `f` turning characters into cells. Empty
+    // lines are skipped and all others must be as long as the first.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for (i, line) in input.lines().enumerate().filter(|(_, l)| !l.is_empty()) {
+            let len = cells.len();
+            for (j, c) in line.chars().enumerate() {
+                cells.push(f(c).map_err(|e| anyhow!("line {}, column {}: {e:#}", i + 1, j + 1))?);
+            }
+            let w = *width.get_or_insert(cells.len() - len);
+            if cells.len() - len != w {
+                bail!(
+                    "line {}: expected {w} cells, got {}",
+                    i + 1,
+                    cells.len() - len
+                );
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if pos.y < self.height && pos.x < self.width {
+            Some(&self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if pos.y < self.height && pos.x < self.width {
+            Some(&mut self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+
+    // `pos` moved by `offset`, like a `Dir4` or `Dir8` one, if that's still inside the grid.
+    pub fn offset(&self, pos: Pos, offset: Point2<isize>) -> Option<Pos> {
+        let x = pos.x.checked_add_signed(offset.x)?;
+        let y = pos.y.checked_add_signed(offset.y)?;
+        (y < self.height && x < self.width).then_some(Point2::new(x, y))
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(pos, dir.offset()))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(pos, dir.offset()))
+    }
+
+    // Cells from `pos`, which isn't included, in steps of `offset` up to the edge of the grid.
+    pub fn ray(&self, pos: Pos, offset: Point2<isize>) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.offset(pos, offset), move |p| self.offset(*p, offset))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(
+            row < self.height,
+            "row {row} outside of {}x{}",
+            self.width,
+            self.height
+        );
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // chunks_exact panics on 0, an empty row has no cells to chunk anyway.
+        self.cells.chunks_exact(self.width.max(1)).take(self.height)
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        // Also keeps step_by from getting a width of 0.
+        assert!(
+            col < self.width,
+            "column {col} outside of {}x{}",
+            self.width,
+            self.height
+        );
+        self.cells[col..]
+            .iter()
+            .step_by(self.width)
+            .take(self.height)
+    }
+
+    // All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point2::new(x, y)))
+    }
+
+    // All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    // Position of the first cell, row by row, for which `f` holds.
+    pub fn position(&self, f: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.cells.iter().position(f).map(|i| self.node(i))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // Adds a row at the bottom.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) {
+        let len = self.cells.len();
+        self.cells.extend(row);
+        assert_eq!(self.width, self.cells.len() - len, "row of the wrong width");
+        self.height += 1;
+    }
+
+    // Copy of the grid with `n` cells of `fill` added on every side, so positions move by `n`
+    // right and down.
+    pub fn pad(&self, n: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut padded = Self::new(self.width + 2 * n, self.height + 2 * n, fill);
+        for (pos, cell) in self.iter() {
+            padded[pos + Point2::new(n, n)] = cell.clone();
+        }
+        padded
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} outside of {}x{}", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} outside of {width}x{height}"))
+    }
+}
+
+// Cells numbered row by row, for the searches in `dijkstra` that keep their state in Vecs.
+impl<T> Indexer<Pos> for Grid<T> {
+    fn node_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn index(&self, pos: &Pos) -> usize {
+        pos.y * self.width + pos.x
+    }
+
+    fn node(&self, index: usize) -> Pos {
+        Point2::new(index % self.width, index / self.width)
+    }
+}
+
+// A line per row, like the character maps the grids are parsed from.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", Ok).unwrap()
+    }
+
+    #[test]
+    fn parse_test() {
+        let g = grid();
+        assert_eq!((3, 2), (g.width(), g.height()));
+        assert_eq!('f', g[Point2::new(2, 1)]);
+        assert_eq!(None, g.get(Point2::new(0, 2)));
+        assert_eq!("abc\ndef", g.to_string());
+        assert_eq!(
+            "line 2: expected 3 cells, got 2",
+            Grid::parse("abc\nde\n", Ok).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 2, column 2: not a digit",
+            Grid::parse("12\n3x\n", |c| c
+                .to_digit(10)
+                .ok_or_else(|| anyhow!("not a digit")))
+            .unwrap_err()
+            .to_string()
+        );
+        assert_eq!(
+            g,
+            Grid::from_rows(vec!["abc".chars().collect(), "def".chars().collect()]).unwrap()
+        );
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+    }
+
+    #[test]
+    fn iter_test() {
+        let g = grid();
+        assert_eq!(
+            vec![Point2::new(1, 0), Point2::new(0, 1)],
+            g.neighbours4(Point2::default()).collect::<Vec<_>>()
+        );
+        assert_eq!(5, g.neighbours8(Point2::new(1, 1)).count());
+        assert_eq!(
+            vec![Point2::new(2, 0)],
+            g.ray(Point2::default(), Point2::new(2, 0))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![Point2::new(1, 0)],
+            g.ray(Point2::new(1, 1), Dir4::Up.offset())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(vec![&'b', &'e'], g.column(1).collect::<Vec<_>>());
+        assert_eq!(&['d', 'e', 'f'], g.row(1));
+        assert_eq!(
+            vec![&['d', 'e', 'f']],
+            g.rows().rev().take(1).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(Point2::new(1, 1)), g.position(|c| *c == 'e'));
+        assert_eq!(5, Indexer::index(&g, &Point2::new(2, 1)));
+        assert_eq!(Point2::new(2, 1), g.node(5));
+        assert_eq!(
+            "...\n.x.",
+            g.map(|c| if *c == 'e' { 'x' } else { '.' }).to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 outside of 3x2")]
+    fn column_outside_test() {
+        let _ = grid().column(3);
+    }
+
+    #[test]
+    fn grow_test() {
+        let mut g = grid();
+        g.push_row("ghi".chars());
+        assert_eq!('i', g[Point2::new(2, 2)]);
+        let g = g.pad(1, '.');
+        assert_eq!(".....\n.abc.\n.def.\n.ghi.\n.....", g.to_string());
+    }
+}
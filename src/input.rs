@@ -1,4 +1,5 @@
-use std::fmt::Debug;
+use anyhow::{anyhow, Result};
+use std::fmt::{Debug, Display};
 use std::str::FromStr;
 
 pub fn tokens<T>(input: &str, sep: Option<&str>) -> Vec<T>
@@ -31,3 +32,175 @@ where
         .map(|sub| tokens(sub, inner_sep))
         .collect()
 }
+
+// Line and column, both counted from 1, at which `part` starts. `part` has to be a slice of
+// `input`.
+fn position(input: &str, part: &str) -> (usize, usize) {
+    let offset = part.as_ptr() as usize - input.as_ptr() as usize;
+    let before = &input[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+fn split<'a>(input: &'a str, sep: Option<&'a str>) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+    match sep {
+        Some(sep) => Box::new(input.split(sep).filter(|v| !v.is_empty())),
+        None => Box::new(input.split_whitespace()),
+    }
+}
+
+fn parse_token<T>(input: &str, token: &str) -> Result<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    token.parse().map_err(|e| {
+        let (line, column) = position(input, token);
+        anyhow!("line {line}, column {column}: invalid token '{token}': {e}")
+    })
+}
+
+// Like `tokens`, but fails on the first token that doesn't parse instead of skipping it.
+pub fn try_tokens<T>(input: &str, sep: Option<&str>) -> Result<Vec<T>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    try_tokens_in(input, input, sep)
+}
+
+// Like `try_tokens` for a section of `input`, so that errors give positions in all of `input`.
+// `part` has to be a slice of `input`.
+pub fn try_tokens_in<T>(input: &str, part: &str, sep: Option<&str>) -> Result<Vec<T>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    split(part, sep).map(|t| parse_token(input, t)).collect()
+}
+
+// Like `token_groups`, but fails on the first token that doesn't parse instead of skipping it.
+pub fn try_token_groups<T>(input: &str, sep: &str, inner_sep: Option<&str>) -> Result<Vec<Vec<T>>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    input
+        .split(sep)
+        .filter(|group| !group.is_empty())
+        .map(|group| {
+            split(group, inner_sep)
+                .map(|t| parse_token(input, t))
+                .collect()
+        })
+        .collect()
+}
+
+// Like `try_token_groups` for groups of exactly `N` tokens, like the coordinates on a line.
+pub fn try_token_arrays<T, const N: usize>(
+    input: &str,
+    sep: &str,
+    inner_sep: Option<&str>,
+) -> Result<Vec<[T; N]>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    input
+        .split(sep)
+        .filter(|group| !group.is_empty())
+        .map(|group| {
+            let tokens: Vec<T> = split(group, inner_sep)
+                .map(|t| parse_token(input, t))
+                .collect::<Result<_>>()?;
+            tokens.try_into().map_err(|tokens: Vec<T>| {
+                let (line, column) = position(input, group);
+                anyhow!(
+                    "line {line}, column {column}: expected {N} tokens, got {} in '{group}'",
+                    tokens.len()
+                )
+            })
+        })
+        .collect()
+}
+
+// Parses every non-empty line with `f`, prefixing its errors with the line number.
+pub fn try_lines<'a, T>(input: &'a str, mut f: impl FnMut(&'a str) -> Result<T>) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.is_empty())
+        .map(|(i, l)| f(l).map_err(|e| anyhow!("line {}: {e:#}", i + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_tokens_test() {
+        assert_eq!(vec![1, 2, 3], try_tokens::<u32>("1 2\n 3\n", None).unwrap());
+        assert_eq!(vec![1, 3], tokens::<u32>("1\nx\n3", Some("\n")));
+        assert_eq!(
+            "line 2, column 1: invalid token 'x': invalid digit found in string",
+            try_tokens::<u32>("1\nx\n3", Some("\n"))
+                .unwrap_err()
+                .to_string()
+        );
+        let input = "a\n\n1\nx\n";
+        assert_eq!(
+            "line 4, column 1: invalid token 'x': invalid digit found in string",
+            try_tokens_in::<u32>(input, &input[3..], Some("\n"))
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn try_token_groups_test() {
+        assert_eq!(
+            vec![vec![1, 2], vec![3]],
+            try_token_groups::<u32>("1\n2\n\n3\n", "\n\n", None).unwrap()
+        );
+        assert_eq!(
+            "line 4, column 3: invalid token '-4': invalid digit found in string",
+            try_token_groups::<u32>("1\n2\n\n3 -4\n", "\n\n", None)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn try_token_arrays_test() {
+        assert_eq!(
+            vec![[1, 2, 3], [4, 5, 6]],
+            try_token_arrays::<i32, 3>("1,2,3\n4,5,6\n", "\n", Some(",")).unwrap()
+        );
+        assert_eq!(
+            "line 2, column 1: expected 3 tokens, got 2 in '4,5'",
+            try_token_arrays::<i32, 3>("1,2,3\n4,5\n", "\n", Some(","))
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "line 1, column 5: invalid token 'ż': invalid digit found in string",
+            try_token_arrays::<i32, 3>("1,2,ż\n", "\n", Some(","))
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn try_lines_test() {
+        let parse = |l: &str| l.parse::<u32>().map_err(|e| anyhow!("'{l}': {e}"));
+        assert_eq!(vec![1, 2], try_lines("1\n\n2\n", parse).unwrap());
+        assert_eq!(
+            "line 3: 'x': invalid digit found in string",
+            try_lines("1\n\nx\n", parse).unwrap_err().to_string()
+        );
+    }
+}
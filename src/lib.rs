@@ -1,30 +1,48 @@
+pub mod answers;
+pub mod baseline;
 pub mod dijkstra;
+pub mod geom;
+pub mod grid;
 pub mod input;
+pub mod mem;
+pub mod progress;
+#[macro_use]
+pub mod registry;
+pub mod report;
+pub mod selection;
+pub mod solution;
 pub mod u8set;
 use u8set::U8Set;
 
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
+pub use registry::{days, get, run, run_in, year, Day};
+
+days! {
+    aoc2022 = 2022 {
+        day01 = 1: "Calorie Counting",
+        day02 = 2: "Rock Paper Scissors",
+        day03 = 3: "Rucksack Reorganization",
+        day04 = 4: "Camp Cleanup",
+        day05 = 5: "Supply Stacks",
+        day06 = 6: "Tuning Trouble",
+        day07 = 7: "No Space Left On Device",
+        day08 = 8: "Treetop Tree House",
+        day09 = 9: "Rope Bridge",
+        day10 = 10: "Cathode-Ray Tube",
+        day11 = 11: "Monkey in the Middle",
+        day12 = 12: "Hill Climbing Algorithm",
+        day13 = 13: "Distress Signal",
+        day14 = 14: "Regolith Reservoir",
+        day15 = 15: "Beacon Exclusion Zone",
+        day16 = 16: "Proboscidea Volcanium",
+        day17 = 17: "Pyroclastic Flow",
+        day18 = 18: "Boiling Boulders",
+        #[ignore = "takes minutes, run with --ignored"]
+        day19 = 19: "Not Enough Minerals",
+        day20 = 20: "Grove Positioning System",
+        day21 = 21: "Monkey Math",
+        day22 = 22: "Monkey Map",
+        day23 = 23: "Unstable Diffusion",
+        day24 = 24: "Blizzard Basin",
+        day25 = 25: "Full of Hot Air",
+    }
+}
@@ -1,38 +1,138 @@
-use anyhow::Result;
-use jemallocator::Jemalloc;
-use memmap::MmapOptions;
+use anyhow::{anyhow, Result};
+use aoc22::answers::{answers_path, batch_inputs, example_inputs, input_dir, Manifest};
+use aoc22::baseline::Baseline;
+use aoc22::mem::{self, Counting, Memory, Meter};
+use aoc22::progress::{Progress, ProgressMode, ProgressSink};
+use aoc22::registry::{self, SolveFn};
+use aoc22::report::{DayRecord, Failure, FailureKind, Format, Report, Sample, Stats, Verification};
+use aoc22::selection::{Days, InputOverride, Source};
+use aoc22::solution::{Answer, Answers, Context, Params, Phase, Timings};
+use memmap::{Mmap, MmapOptions};
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use serde::Serialize;
 use std::fs::File;
+use std::io::{IsTerminal, Read};
+use std::panic::AssertUnwindSafe;
 use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::time::Instant;
 use structopt::StructOpt;
+use tikv_jemallocator::Jemalloc;
 
 #[global_allocator]
-static GLOBAL: Jemalloc = Jemalloc;
+static GLOBAL: Counting<Jemalloc> = Counting(Jemalloc);
+
+// Stack of the threads days run on, the main thread's size since some days recurse deep.
+const STACK_SIZE: usize = 8 << 20;
 
 #[derive(StructOpt, Debug)]
 #[structopt(author)]
 struct Opt {
+    /// Don't check the answers against --answers.
     #[structopt(short, long)]
     skip_verification: bool,
 
+    /// Year whose days to run, the latest one by default.
     #[structopt(short, long)]
-    day_to_run: Option<usize>,
+    year: Option<u16>,
+
+    /// Days and ranges of days to run, like 1,3,10-15. All days of the year by default.
+    #[structopt(short, long, alias = "day-to-run")]
+    days: Option<Days>,
 
+    /// Input of the single selected day, - reads it from stdin.
     #[structopt(short, long)]
-    input_file: Option<PathBuf>,
+    input_file: Option<Source>,
+
+    /// Directory with the dayNN inputs and dayNN_example*.txt examples, inputs/YEAR by default.
+    #[structopt(long)]
+    input_dir: Option<PathBuf>,
+
+    /// Input of one day as DAY=PATH, - reads it from stdin. May be given for several days.
+    #[structopt(long = "input", number_of_values = 1)]
+    inputs: Vec<InputOverride>,
 
+    /// Run each day once on every dayNN_example*.txt in --input-dir instead of the real input.
+    #[structopt(long, conflicts_with_all = &["input-file", "inputs"])]
+    examples: bool,
+
+    /// Run the single selected day on every file in this directory, like inputs collected from
+    /// different accounts, checking each against the answers of the day named after the file.
+    #[structopt(long, conflicts_with_all = &["input-file", "inputs", "examples"])]
+    batch: Option<PathBuf>,
+
+    /// Don't print the answers.
     #[structopt(long)]
     skip_output: bool,
+
+    /// Untimed runs of each day before sampling.
+    #[structopt(long, default_value = "0")]
+    warmup: usize,
+
+    /// Timed runs of each day, 10 by default in release builds and 1 in debug builds or with
+    /// --examples.
+    #[structopt(long, parse(try_from_str = parse_samples))]
+    samples: Option<usize>,
+
+    /// Seconds after which no further samples of a day are taken.
+    #[structopt(long, default_value = "10")]
+    time_budget: f64,
+
+    /// Run this many days at a time. Faster for checking answers, but days compete for cores so
+    /// timings are only accurate with the default of 1.
+    #[structopt(long, default_value = "1")]
+    jobs: usize,
+
+    /// Seconds a day may run, warm-up and all samples included, before it is reported as
+    /// timed out and the next day is run. Without it days run on the main thread.
+    #[structopt(long)]
+    timeout: Option<f64>,
+
+    /// Expected answers of the inputs and examples, answers/YEAR.toml by default.
+    #[structopt(long)]
+    answers: Option<PathBuf>,
+
+    /// Store per-day timing statistics of this run under a name in --baseline-dir, replacing
+    /// previously saved stats of the days that were run.
+    #[structopt(long)]
+    save_baseline: Option<String>,
+
+    /// Compare per-day median timings with a saved baseline, failing if any day got slower
+    /// by more than --threshold percent.
+    #[structopt(long)]
+    compare_baseline: Option<String>,
+
+    /// Directory the baselines are saved in, one json file per name.
+    #[structopt(long, default_value = "target/baselines")]
+    baseline_dir: PathBuf,
+
+    /// Percent by which a day may get slower than in --compare-baseline.
+    #[structopt(long, default_value = "10")]
+    threshold: f64,
+
+    /// Report allocations of each day's first sample on all threads, its peak heap and the
+    /// memory jemalloc keeps resident after it.
+    #[structopt(long)]
+    mem: bool,
+
+    /// Progress of long searches on stderr: live status line, json lines or none. Auto is live
+    /// when stderr is a terminal. Live is turned off with --jobs above 1, as the status lines of
+    /// days running at once would overwrite each other.
+    #[structopt(long, default_value = "auto")]
+    progress: ProgressMode,
+
+    /// text, json or csv. Reports go to stdout, diagnostics to stderr.
+    #[structopt(long, default_value = "text")]
+    format: Format,
 }
 
-fn median(array: &[Duration]) -> Duration {
-    if (array.len() % 2) == 0 {
-        let ind_left = array.len() / 2 - 1;
-        let ind_right = array.len() / 2;
-        (array[ind_left] + array[ind_right]).div_f64(2.0)
-    } else {
-        array[array.len() / 2]
+fn parse_samples(s: &str) -> Result<usize> {
+    match s.parse()? {
+        0 => Err(anyhow!("at least 1 sample is needed")),
+        n => Ok(n),
     }
 }
 
@@ -40,119 +140,709 @@ fn d2s(d: Duration) -> String {
     format!("{:.1?}", d)
 }
 
+fn b2s(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < units.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1}{}", units[unit])
+}
+
+fn print_answers(answers: &Answers) {
+    for answer in [&answers.part1, &answers.part2] {
+        match answer {
+            Answer::Empty => {}
+            Answer::Multiline(s) => println!("{s}"),
+            answer => println!("\t{answer}"),
+        }
+    }
+}
+
+enum RunError {
+    TimedOut,
+    Failed(FailureKind, String),
+}
+
+fn failed(e: anyhow::Error) -> RunError {
+    let kind = match e.downcast_ref::<Phase>() {
+        Some(Phase::Parse) => FailureKind::Parse,
+        _ => FailureKind::Solution,
+    };
+    RunError::Failed(kind, format!("{e:#}"))
+}
+
+// Answers and timings of a run, with its memory use if asked for.
+type Outcome = (Answers, Timings, Option<Memory>);
+
+fn call(
+    solution: SolveFn,
+    input: &[u8],
+    ctx: &Context,
+    measure: bool,
+) -> Result<Outcome, RunError> {
+    let input = std::str::from_utf8(input)
+        .map_err(|e| RunError::Failed(FailureKind::Input, format!("not utf-8: {e}")))?;
+    // On the thread the solution runs on, jemalloc counts allocations per thread.
+    let meter = measure.then(Meter::start);
+    match std::panic::catch_unwind(AssertUnwindSafe(|| solution(input, ctx))) {
+        Ok(result) => result
+            .map(|(answers, t)| (answers, t, meter.map(Meter::finish)))
+            .map_err(failed),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(RunError::Failed(
+                FailureKind::Solution,
+                format!("panicked: {message}"),
+            ))
+        }
+    }
+}
+
+// Stdin can be read only once, so it is read on first use and shared by all runs and days.
+static STDIN: Lazy<Result<Arc<[u8]>, String>> = Lazy::new(|| {
+    let mut buf = vec![];
+    std::io::stdin()
+        .read_to_end(&mut buf)
+        .map(|_| buf.into())
+        .map_err(|e| format!("failed to read stdin: {e}"))
+});
+
+enum Input {
+    File(File),
+    // Already in memory, so with i/o timings of stdin inputs don't include reading them.
+    Stdin(Arc<[u8]>),
+}
+
+impl Input {
+    fn open(source: &Source) -> Result<Self, String> {
+        match source {
+            Source::File(path) => File::open(path)
+                .map(Input::File)
+                .map_err(|e| format!("failed to open '{}': {e}", path.display())),
+            Source::Stdin => STDIN.clone().map(Input::Stdin),
+        }
+    }
+
+    fn load(&self) -> Result<Loaded, RunError> {
+        match self {
+            // Mapping an empty file fails, an empty input is still a valid one.
+            Input::File(f) if f.metadata().map(|m| m.len()).unwrap_or(0) == 0 => {
+                Ok(Loaded::Mapped(None))
+            }
+            Input::File(f) => unsafe { MmapOptions::new().map(f) }
+                .map(|m| Loaded::Mapped(Some(m)))
+                .map_err(|e| RunError::Failed(FailureKind::Input, e.to_string())),
+            Input::Stdin(bytes) => Ok(Loaded::Stdin(bytes.clone())),
+        }
+    }
+}
+
+enum Loaded {
+    Mapped(Option<Mmap>),
+    Stdin(Arc<[u8]>),
+}
+
+impl Loaded {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Loaded::Mapped(m) => m.as_deref().unwrap_or_default(),
+            Loaded::Stdin(bytes) => bytes,
+        }
+    }
+}
+
+// Runs the solution once, in a worker if there is a deadline. If the deadline passes first the
+// worker is told to cancel and left behind.
+fn run_once(
+    solution: SolveFn,
+    input: &Input,
+    ctx: Context,
+    deadline: Option<Instant>,
+    measure: bool,
+) -> Result<(Outcome, Duration), RunError> {
+    let start = Instant::now();
+    let loaded = input.load()?;
+    let Some(deadline) = deadline else {
+        let outcome = call(solution, loaded.bytes(), &ctx, measure)?;
+        return Ok((outcome, start.elapsed()));
+    };
+
+    let worker_ctx = ctx.clone();
+    let (tx, rx) = mpsc::channel();
+    std::thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let _ = tx.send(call(solution, loaded.bytes(), &worker_ctx, measure));
+        })
+        .map_err(|e| RunError::Failed(FailureKind::Solution, e.to_string()))?;
+    match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+        Ok(result) => result.map(|outcome| (outcome, start.elapsed())),
+        Err(RecvTimeoutError::Disconnected) => Err(RunError::Failed(
+            FailureKind::Solution,
+            "worker died".to_owned(),
+        )),
+        Err(RecvTimeoutError::Timeout) => {
+            ctx.cancel();
+            Err(RunError::TimedOut)
+        }
+    }
+}
+
+// Shows progress of the day being run on stderr, at most every `interval` and only once it
+// has run for that long.
+#[derive(Debug)]
+struct Reporter {
+    mode: ProgressMode,
+    day: usize,
+    input_name: String,
+    interval: Duration,
+    start: Instant,
+    // When progress was last shown.
+    last: Mutex<Option<Instant>>,
+}
+
+#[derive(Serialize)]
+struct ProgressLine<'a> {
+    day: usize,
+    input: &'a str,
+    elapsed_ms: u128,
+    #[serde(flatten)]
+    progress: &'a Progress,
+}
+
+impl Reporter {
+    fn new(mode: ProgressMode, day: usize, input_name: &str) -> Option<Arc<Self>> {
+        let interval = match mode {
+            ProgressMode::Live => Duration::from_millis(100),
+            ProgressMode::Json => Duration::from_secs(1),
+            ProgressMode::Auto | ProgressMode::None => return None,
+        };
+        Some(Arc::new(Self {
+            mode,
+            day,
+            input_name: input_name.to_owned(),
+            interval,
+            start: Instant::now(),
+            last: Mutex::new(None),
+        }))
+    }
+
+    // Clears the status line, if one was drawn.
+    fn finish(&self) {
+        if self.mode == ProgressMode::Live && self.last.lock().unwrap().is_some() {
+            eprint!("\r\x1b[K");
+        }
+    }
+}
+
+impl ProgressSink for Reporter {
+    fn report(&self, progress: &Progress) {
+        let mut last = self.last.lock().unwrap();
+        if last.unwrap_or(self.start).elapsed() < self.interval {
+            return;
+        }
+        *last = Some(Instant::now());
+        let elapsed = self.start.elapsed();
+        if self.mode == ProgressMode::Json {
+            let line = ProgressLine {
+                day: self.day,
+                input: &self.input_name,
+                elapsed_ms: elapsed.as_millis(),
+                progress,
+            };
+            eprintln!("{}", serde_json::to_string(&line).unwrap());
+            return;
+        }
+        let best = progress.best.map(|b| b.to_string()).unwrap_or_default();
+        eprint!(
+            "\r\x1b[KDay {:02} ({}) {:>9}  states {}  frontier {}  best {}  pruned {}",
+            self.day,
+            self.input_name,
+            d2s(elapsed),
+            progress.states,
+            progress.frontier,
+            best,
+            progress.pruned
+        );
+    }
+}
+
+// How many times and for how long each day runs.
+struct Sampling {
+    warmup: usize,
+    samples: usize,
+    time_budget: Duration,
+    timeout: Option<Duration>,
+    // Resolved, never Auto.
+    progress: ProgressMode,
+    mem: bool,
+}
+
+// Everything runs of one day on one input produced, printed once all of them are done.
+struct DayRun {
+    day: usize,
+    source: Source,
+    input_name: String,
+    // From the first successful run.
+    answers: Option<Answers>,
+    samples: Vec<(Timings, Duration)>,
+    memory: Option<Memory>,
+    // Runs stop at the first failure, solutions are deterministic so retrying is pointless.
+    failure: Option<(FailureKind, String)>,
+}
+
+fn run_day(
+    solution: SolveFn,
+    day: usize,
+    source: Source,
+    params: Params,
+    sampling: &Sampling,
+) -> DayRun {
+    let mut run = DayRun {
+        day,
+        input_name: source.name(),
+        source,
+        answers: None,
+        samples: vec![],
+        memory: None,
+        failure: None,
+    };
+    let input = match Input::open(&run.source) {
+        Ok(input) => input,
+        Err(message) => {
+            run.failure = Some((FailureKind::Input, message));
+            return run;
+        }
+    };
+
+    let timeout_failure = |timeout: Duration| {
+        let message = format!("no result after {timeout:.1?}");
+        Some((FailureKind::TimedOut, message))
+    };
+    let deadline = sampling.timeout.map(|t| Instant::now() + t);
+    let reporter = Reporter::new(sampling.progress, day, &run.input_name);
+    let ctx = || {
+        let ctx = match &reporter {
+            Some(reporter) => Context::with_progress(reporter.clone()),
+            None => Context::default(),
+        };
+        ctx.with_params(params.clone())
+    };
+    for _ in 0..sampling.warmup {
+        match run_once(solution, &input, ctx(), deadline, false) {
+            Ok(_) => {}
+            Err(RunError::TimedOut) => {
+                run.failure = timeout_failure(sampling.timeout.unwrap());
+                break;
+            }
+            Err(RunError::Failed(kind, message)) => {
+                run.failure = Some((kind, message));
+                break;
+            }
+        }
+    }
+    let samples = if run.failure.is_some() {
+        0
+    } else {
+        sampling.samples
+    };
+    let sampling_start = Instant::now();
+    for _ in 0..samples {
+        // Solutions are deterministic, so are their allocations.
+        let measure = sampling.mem && run.samples.is_empty();
+        match run_once(solution, &input, ctx(), deadline, measure) {
+            Ok(((answers, t, memory), with_io)) => {
+                if measure {
+                    run.memory = memory;
+                }
+                if run.answers.is_none() {
+                    run.answers = Some(answers);
+                }
+                run.samples.push((t, with_io));
+            }
+            Err(RunError::TimedOut) => {
+                // Samples taken so far are good enough, only a day without any is reported.
+                if run.samples.is_empty() {
+                    run.failure = timeout_failure(sampling.timeout.unwrap());
+                }
+                break;
+            }
+            Err(RunError::Failed(kind, message)) => {
+                run.failure = Some((kind, message));
+                break;
+            }
+        }
+        if sampling_start.elapsed() > sampling.time_budget {
+            break;
+        }
+    }
+    if let Some(reporter) = reporter {
+        reporter.finish();
+    }
+    run
+}
+
 fn main() {
     let opt = Opt::from_args();
-    let mut times = vec![];
-    let mut times_io = vec![];
-
-    let solutions: Vec<&dyn Fn(&str, bool, bool) -> Result<Duration>> = vec![
-        &aoc22::day01::solve,
-        &aoc22::day02::solve,
-        &aoc22::day03::solve,
-        &aoc22::day04::solve,
-        &aoc22::day05::solve,
-        &aoc22::day06::solve,
-        &aoc22::day07::solve,
-        &aoc22::day08::solve,
-        &aoc22::day09::solve,
-        &aoc22::day10::solve,
-        &aoc22::day11::solve,
-        &aoc22::day12::solve,
-        &aoc22::day13::solve,
-        &aoc22::day14::solve,
-        &aoc22::day15::solve,
-        &aoc22::day16::solve,
-        &aoc22::day17::solve,
-        &aoc22::day18::solve,
-        &aoc22::day19::solve,
-        &aoc22::day20::solve,
-        &aoc22::day21::solve,
-        &aoc22::day22::solve,
-        &aoc22::day23::solve,
-        &aoc22::day24::solve,
-        &aoc22::day25::solve,
-    ];
+    let mut failures: Vec<Failure> = vec![];
 
-    let mut running_sum_compute = Duration::from_secs(0);
-    let mut running_sum_io = Duration::from_secs(0);
-    for (i, solution) in solutions.iter().enumerate() {
-        if Some(i + 1) == opt.day_to_run || opt.day_to_run.is_none() {
-            let input_file = match &opt.input_file {
-                Some(path) => File::open(path).unwrap(),
-                None => File::open(format!("inputs/day{:02}", i + 1)).unwrap(),
-            };
+    let year = opt.year.unwrap_or_else(registry::latest_year);
+    let Some(solutions) = registry::year(year) else {
+        eprintln!(
+            "no solutions for {year}, only for {}",
+            registry::years()
+                .map(|y| y.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        std::process::exit(1);
+    };
+    let input_dir = opt.input_dir.clone().unwrap_or_else(|| input_dir(year));
+    let answers = opt.answers.clone().unwrap_or_else(|| answers_path(year));
+
+    // Also holds the params of the inputs, which are needed even if answers aren't checked.
+    let manifest = match Manifest::load(&answers) {
+        Ok(manifest) => manifest,
+        Err(_) if opt.skip_verification => Manifest::default(),
+        Err(e) => {
+            eprintln!("{e:#}");
+            std::process::exit(1);
+        }
+    };
+
+    let text = opt.format == Format::Text;
+    let default_samples = if cfg!(debug_assertions) || opt.examples {
+        1
+    } else {
+        10
+    };
+    let sampling = Sampling {
+        warmup: opt.warmup,
+        samples: opt.samples.unwrap_or(default_samples),
+        time_budget: Duration::from_secs_f64(opt.time_budget),
+        timeout: opt.timeout.map(Duration::from_secs_f64),
+        mem: opt.mem,
+        progress: match opt.progress {
+            ProgressMode::Auto | ProgressMode::Live if opt.jobs > 1 => ProgressMode::None,
+            ProgressMode::Auto if std::io::stderr().is_terminal() => ProgressMode::Live,
+            ProgressMode::Auto => ProgressMode::None,
+            mode => mode,
+        },
+    };
+    if opt.mem {
+        if opt.jobs > 1 {
+            eprintln!(
+                "--mem needs --jobs 1, allocations of days running at once can't be told apart"
+            );
+            std::process::exit(1);
+        }
+        if let Err(e) = mem::enable() {
+            eprintln!("{e:#}");
+            std::process::exit(1);
+        }
+    }
 
-            let mut solution_times = vec![];
-            #[cfg(debug_assertions)]
-            let loops = 1;
-            #[cfg(not(debug_assertions))]
-            let loops = 10;
-            for i in 0..loops {
-                let start = Instant::now();
-                let mapped_input = unsafe { MmapOptions::new().map(&input_file).unwrap() };
-                let input = std::str::from_utf8(&mapped_input).unwrap();
-
-                let t = match solution(
-                    input,
-                    !opt.skip_verification,
-                    if i == 0 { !opt.skip_output } else { false },
-                ) {
-                    Ok(t) => t,
-                    Err(e) => {
-                        eprintln!("Solution {i} failed: {e}");
-                        continue;
-                    }
-                };
-
-                solution_times.push((t, start.elapsed()));
-                if t > Duration::from_secs(1) {
-                    break;
+    let days = match &opt.days {
+        Some(days) => days.0.clone(),
+        None => solutions.iter().map(|d| d.day).collect(),
+    };
+    if let Some(day) = days
+        .iter()
+        .find(|day| registry::get_in(year, **day).is_none())
+    {
+        eprintln!("no solution for day {day} of {year}");
+        std::process::exit(1);
+    }
+    if opt.input_file.is_some() && days.len() != 1 {
+        eprintln!("--input-file needs a single day, use --input DAY=PATH for several days");
+        std::process::exit(1);
+    }
+    if opt.batch.is_some() && days.len() != 1 {
+        eprintln!("--batch needs a single day, e.g. -d 22");
+        std::process::exit(1);
+    }
+    let mut tasks = vec![];
+    for &day in &days {
+        let sources = if opt.examples {
+            match example_inputs(&input_dir, day) {
+                Ok(paths) => paths.into_iter().map(Source::File).collect(),
+                Err(e) => {
+                    failures.push(Failure {
+                        day,
+                        input: "examples".to_owned(),
+                        kind: FailureKind::Input,
+                        message: format!("{e:#}"),
+                    });
+                    continue;
                 }
             }
-            let (t, solution_with_io) = solution_times.into_iter().min().unwrap();
-            running_sum_compute += t;
-            running_sum_io += solution_with_io;
+        } else if let Some(dir) = &opt.batch {
+            match batch_inputs(dir) {
+                Ok(paths) => paths.into_iter().map(Source::File).collect(),
+                Err(e) => {
+                    eprintln!("{e:#}");
+                    std::process::exit(1);
+                }
+            }
+        } else if let Some(source) = &opt.input_file {
+            vec![source.clone()]
+        } else if let Some(o) = opt.inputs.iter().rev().find(|o| o.day == day) {
+            vec![o.source.clone()]
+        } else {
+            vec![Source::default_for(&input_dir, day)]
+        };
+        tasks.extend(sources.into_iter().map(|source| (day, source)));
+    }
+    let run = |(day, source): (usize, Source)| {
+        let params = manifest
+            .expected(day, &source.name())
+            .map(|e| e.params.clone())
+            .unwrap_or_default();
+        let solve = registry::get_in(year, day).unwrap().solve;
+        run_day(solve, day, source, params, &sampling)
+    };
+    // Serial runs are printed as they finish, parallel ones once all are done, in order.
+    let runs: Box<dyn Iterator<Item = DayRun>> = if opt.jobs > 1 {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(opt.jobs)
+            .stack_size(STACK_SIZE)
+            .build()
+            .unwrap();
+        let runs: Vec<DayRun> = pool.install(|| tasks.into_par_iter().map(run).collect());
+        Box::new(runs.into_iter())
+    } else {
+        Box::new(tasks.into_iter().map(run))
+    };
+
+    let mut records = vec![];
+    // Input name, status and compute time of every batch input.
+    let mut batch_rows: Vec<(String, Result<Verification, FailureKind>, Option<Duration>)> = vec![];
+    let mut running_sum_compute = Duration::from_secs(0);
+    let mut running_sum_io = Duration::from_secs(0);
+    for run in runs {
+        let (day, input_name) = (run.day, run.input_name);
+        if (opt.examples || opt.batch.is_some()) && text {
+            println!("Day {day:02} ({input_name})");
+        }
+        let failed = run.failure.as_ref().map(|(kind, _)| *kind);
+        if let Some((kind, message)) = run.failure {
+            eprintln!("Day {day:02} ({input_name}) {kind}: {message}");
+            failures.push(Failure {
+                day,
+                input: input_name.clone(),
+                kind,
+                message,
+            });
+        }
+        let Some(answers) = run.answers else {
+            batch_rows.push((input_name, Err(failed.unwrap()), None));
+            continue;
+        };
+        let mut verification = Verification::Unchecked;
+        if let Some(expected) = manifest
+            .expected(day, &input_name)
+            .filter(|_| !opt.skip_verification)
+        {
+            verification = Verification::Ok;
+            for mismatch in expected.check(&answers) {
+                eprintln!("Day {day:02} ({input_name}) {mismatch}");
+                failures.push(Failure {
+                    day,
+                    input: input_name.clone(),
+                    kind: FailureKind::Mismatch,
+                    message: mismatch.to_string(),
+                });
+                verification = Verification::Mismatch;
+            }
+        }
+        if !opt.skip_output && text {
+            print_answers(&answers);
+        }
+
+        let samples: Vec<Sample> = run
+            .samples
+            .iter()
+            .map(|&(t, with_io)| Sample {
+                parse: t.parse,
+                part1: t.part1,
+                part2: t.part2,
+                compute: t.compute(),
+                with_io,
+            })
+            .collect();
+        let fastest = *samples
+            .iter()
+            .min_by_key(|s| (s.compute, s.with_io))
+            .unwrap();
+        let compute: Vec<Duration> = samples.iter().map(|s| s.compute).collect();
+        let compute_stats = Stats::new(&compute).unwrap();
+        let (t, solution_with_io) = (fastest.compute, fastest.with_io);
+        running_sum_compute += t;
+        running_sum_io += solution_with_io;
+        if text {
             println!(
-                "Day {:02} took {:>9} to compute (rsum {:>9}) (with i/o: {:>9}, rsum {:>9})",
-                i + 1,
+                "Day {:02} took {:>9} to compute (rsum {:>9}) (with i/o: {:>9}, rsum {:>9}) (parse: {:>9}, part 1: {:>9}, part 2: {:>9})",
+                day,
                 d2s(t),
                 d2s(running_sum_compute),
                 d2s(solution_with_io),
-                d2s(running_sum_io)
+                d2s(running_sum_io),
+                d2s(fastest.parse),
+                d2s(fastest.part1),
+                d2s(fastest.part2),
             );
-            times.push(t);
-            times_io.push(solution_with_io);
-        }
-    }
-
-    times.sort();
-    times_io.sort();
-
-    let total = times.iter().sum();
-    let min = times.iter().min();
-    let max = times.iter().max();
-
-    let total_io = times_io.iter().sum();
-    let min_io = times_io.iter().min();
-    let max_io = times_io.iter().max();
-    if opt.day_to_run.is_none() {
-        println!(
-            "\n         Total time for {} days: {:>9} (avg per day {:>9}, med: {:>9}, min: {:>9}, max: {:>9})",
-            solutions.len(),
-            d2s(total),
-            d2s(total.div_f64(solutions.len() as f64)),
-            d2s(median(&times)),
-            d2s(*min.unwrap()),
-            d2s(*max.unwrap()),
-        );
-        println!(
-            "Total time with i/o for {} days: {:>9} (avg per day {:>9}, med: {:>9}, min: {:>9}, max: {:>9})",
-            solutions.len(),
-            d2s(total_io),
-            d2s(total_io.div_f64(solutions.len() as f64)),
-            d2s(median(&times_io)),
-            d2s(*min_io.unwrap()),
-            d2s(*max_io.unwrap()),
-        );
+            if samples.len() > 1 {
+                println!(
+                    "       compute over {} samples: min {}, median {}, mean {}, stddev {}, p95 {}",
+                    samples.len(),
+                    d2s(compute_stats.min),
+                    d2s(compute_stats.median),
+                    d2s(compute_stats.mean),
+                    d2s(compute_stats.stddev),
+                    d2s(compute_stats.p95),
+                );
+            }
+            if let Some(m) = run.memory {
+                println!(
+                    "       memory: {} allocations of {}, peak heap {}, resident {} after the run",
+                    m.allocations,
+                    b2s(m.allocated_bytes),
+                    b2s(m.peak_heap_bytes),
+                    b2s(m.resident_bytes),
+                );
+            }
+        }
+        let status = failed.map_or(Ok(verification), Err);
+        batch_rows.push((input_name, status, Some(t)));
+        records.push(DayRecord {
+            day,
+            input: run.source.to_string(),
+            part1: answers.part1.to_string(),
+            part2: answers.part2.to_string(),
+            verification,
+            fastest,
+            compute_stats,
+            samples,
+            memory: run.memory,
+        });
+    }
+
+    // Failures of the examples dir come before any day ran.
+    failures.sort_by_key(|f| f.day);
+    let report = Report::new(records, failures);
+    match opt.format {
+        Format::Text => {
+            if let (Some(compute), Some(with_io), None, false) =
+                (report.compute, report.with_io, &opt.days, opt.examples)
+            {
+                let days = report.days.len();
+                println!(
+                    "\n         Total time for {} days: {:>9} (avg per day {:>9}, med: {:>9}, min: {:>9}, max: {:>9})",
+                    days,
+                    d2s(compute.total),
+                    d2s(compute.avg),
+                    d2s(compute.median),
+                    d2s(compute.min),
+                    d2s(compute.max),
+                );
+                println!(
+                    "Total time with i/o for {} days: {:>9} (avg per day {:>9}, med: {:>9}, min: {:>9}, max: {:>9})",
+                    days,
+                    d2s(with_io.total),
+                    d2s(with_io.avg),
+                    d2s(with_io.median),
+                    d2s(with_io.min),
+                    d2s(with_io.max),
+                );
+            }
+            if let (Some(dir), [day]) = (&opt.batch, &days[..]) {
+                let passed = batch_rows
+                    .iter()
+                    .filter(|(_, s, _)| *s == Ok(Verification::Ok))
+                    .count();
+                println!(
+                    "\nDay {day:02} on {} inputs in '{}': {passed} ok",
+                    batch_rows.len(),
+                    dir.display()
+                );
+                for (input_name, status, t) in &batch_rows {
+                    let status = match status {
+                        Ok(verification) => verification.to_string(),
+                        Err(kind) => kind.to_string(),
+                    };
+                    let t = t.map(d2s).unwrap_or_default();
+                    println!("  {input_name:<24} {status:<9} {t:>9}");
+                }
+            }
+        }
+        Format::Json => println!("{}", report.to_json().unwrap()),
+        Format::Csv => print!("{}", report.to_csv().unwrap()),
+    }
+
+    let current = Baseline::from_report(&report);
+    let mut regressions = vec![];
+    if let Some(name) = &opt.compare_baseline {
+        let baseline = match Baseline::load(&Baseline::path(&opt.baseline_dir, name)) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                eprintln!("{e:#}");
+                std::process::exit(1);
+            }
+        };
+        regressions = baseline.regressions(&current, opt.threshold / 100.0);
+        if regressions.is_empty() {
+            eprintln!(
+                "No day is more than {}% slower than baseline '{name}'",
+                opt.threshold
+            );
+        } else {
+            eprintln!(
+                "\n{} day(s) more than {}% slower than baseline '{name}':",
+                regressions.len(),
+                opt.threshold
+            );
+            for regression in &regressions {
+                eprintln!("{regression}");
+            }
+        }
+    }
+    if let Some(name) = &opt.save_baseline {
+        let path = Baseline::path(&opt.baseline_dir, name);
+        // Merged into the saved one, which is left alone if it can't be read rather than
+        // replaced by only the days of this run.
+        let mut baseline = if path.exists() {
+            match Baseline::load(&path) {
+                Ok(baseline) => baseline,
+                Err(e) => {
+                    eprintln!("{e:#}");
+                    std::process::exit(1);
+                }
+            }
+        } else {
+            Baseline::default()
+        };
+        baseline.merge(current);
+        if let Err(e) = baseline.save(&path) {
+            eprintln!("{e:#}");
+            std::process::exit(1);
+        }
+        eprintln!("Saved baseline '{name}' to '{}'", path.display());
+    }
+
+    if text && !report.failures.is_empty() {
+        println!("\n{} failure(s):", report.failures.len());
+        for failure in &report.failures {
+            println!("{failure}");
+        }
+    }
+    if !report.failures.is_empty() || !regressions.is_empty() {
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,142 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::alloc::{GlobalAlloc, Layout};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tikv_jemalloc_ctl::{epoch, stats};
+
+// Wraps the global allocator to count the allocations of every thread once `enable` is called.
+// Counters are process wide, so they only describe a single day when days run one at a time.
+pub struct Counting<A>(pub A);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+fn on_alloc(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+}
+
+fn on_dealloc(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        // Blocks allocated before counting was enabled are freed too.
+        let _ = LIVE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |live| {
+            Some(live.saturating_sub(size as u64))
+        });
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for Counting<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.0.alloc(layout);
+        if !ptr.is_null() {
+            on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.0.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout);
+        on_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.0.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            on_dealloc(layout.size());
+            on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Memory {
+    // Allocations and reallocations made by the day, on all threads.
+    pub allocations: u64,
+    // Bytes requested by them, freed or not.
+    pub allocated_bytes: u64,
+    // Most heap in use at once while the day ran, above what was in use when it started.
+    pub peak_heap_bytes: u64,
+    // Bytes in pages jemalloc keeps resident once the day is done, also from earlier days.
+    pub resident_bytes: u64,
+}
+
+// Starts counting allocations. Fails if jemalloc doesn't keep statistics, so that `Meter` can
+// count on them.
+pub fn enable() -> Result<()> {
+    epoch::advance().map_err(|e| anyhow!("jemalloc statistics are not available: {e}"))?;
+    ENABLED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+// Measures the memory use of whatever runs between `start` and `finish`.
+pub struct Meter {
+    base: u64,
+}
+
+impl Meter {
+    pub fn start() -> Self {
+        ALLOCATIONS.store(0, Ordering::Relaxed);
+        ALLOCATED.store(0, Ordering::Relaxed);
+        let base = LIVE.load(Ordering::Relaxed);
+        PEAK.store(base, Ordering::Relaxed);
+        Self { base }
+    }
+
+    pub fn finish(self) -> Memory {
+        // Process wide statistics are cached until the epoch advances.
+        let _ = epoch::advance();
+        Memory {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            allocated_bytes: ALLOCATED.load(Ordering::Relaxed),
+            peak_heap_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(self.base),
+            resident_bytes: stats::resident::read().unwrap_or(0) as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tikv_jemallocator::Jemalloc;
+
+    #[test]
+    fn counting_test() {
+        let a = Counting(Jemalloc);
+        enable().unwrap();
+        let meter = Meter::start();
+        let layout = Layout::from_size_align(1000, 8).unwrap();
+        std::thread::scope(|s| {
+            s.spawn(|| unsafe {
+                let p = a.alloc(layout);
+                let p = a.realloc(p, layout, 3000);
+                a.dealloc(p, Layout::from_size_align(3000, 8).unwrap());
+            });
+        });
+        unsafe {
+            let q = a.alloc(layout);
+            a.dealloc(q, layout);
+        }
+        let m = meter.finish();
+        assert_eq!(3, m.allocations);
+        assert_eq!(5000, m.allocated_bytes);
+        assert_eq!(3000, m.peak_heap_bytes);
+        assert!(m.resident_bytes > 0);
+    }
+}
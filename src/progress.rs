@@ -0,0 +1,50 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+// Snapshot of a long search, reported by solutions through `Context::report`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Progress {
+    // States taken off the frontier so far.
+    pub states: u64,
+    // States waiting to be explored.
+    pub frontier: u64,
+    // Best score found so far, if the search has one.
+    pub best: Option<i64>,
+    // States dropped because they can't beat what was already seen.
+    pub pruned: u64,
+}
+
+// Receives progress of the solution it was handed to with `Context::with_progress`. Solutions
+// report often, so implementations should be cheap and throttle their own output.
+pub trait ProgressSink: Debug + Send + Sync {
+    fn report(&self, progress: &Progress);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressMode {
+    // Live if stderr is a terminal, none otherwise.
+    Auto,
+    // Status line on stderr, redrawn in place.
+    Live,
+    // A json object per line on stderr.
+    Json,
+    None,
+}
+
+impl FromStr for ProgressMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "live" => Ok(Self::Live),
+            "json" => Ok(Self::Json),
+            "none" => Ok(Self::None),
+            _ => Err(anyhow!(
+                "unknown progress mode '{s}', expected auto, live, json or none"
+            )),
+        }
+    }
+}
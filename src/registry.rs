@@ -0,0 +1,215 @@
+use anyhow::{anyhow, Result};
+use std::any::Any;
+
+use crate::solution::{Answer, Answers, AnyInput, Context, Timings};
+
+// Parses the input and solves both parts, timing each phase.
+pub type SolveFn = fn(&str, &Context) -> Result<(Answers, Timings)>;
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: usize,
+    pub title: &'static str,
+    pub solve: SolveFn,
+    // Phases of `solve` one by one, for benchmarks.
+    pub parse: fn(&str) -> Result<AnyInput>,
+    pub part1: fn(&dyn Any, &Context) -> Result<Answer>,
+    pub part2: fn(&dyn Any, &Context) -> Result<Answer>,
+}
+
+impl Day {
+    // Inputs that come with numbers besides the text, like day 15's example, pass them in
+    // `ctx`, see `Context::with_params`.
+    pub fn run(&self, input: &str, ctx: &Context) -> Result<Answers> {
+        (self.solve)(input, ctx).map(|(answers, _)| answers)
+    }
+
+    // Module name, also the name of the day's input file.
+    pub fn name(&self) -> String {
+        format!("day{:02}", self.day)
+    }
+}
+
+// Registers the solutions of every year: declares a module per year with a module per day,
+// collects the days into a static slice of `Day`s in the year's module and every year into
+// `YEARS`, and generates a test per day checking its examples against the year's answers.
+// Attributes before a day, like #[ignore], go on its example test.
+//
+//     days! {
+//         aoc2022 = 2022 {
+//             day01 = 1: "Calorie Counting",
+//         }
+//     }
+macro_rules! days {
+    ($($year_module:ident = $year:literal {
+        $($(#[$attr:meta])* $module:ident = $day:literal: $title:literal,)+
+    })+) => {
+        $(
+            pub mod $year_module {
+                $(pub mod $module;)+
+
+                pub static DAYS: &[$crate::registry::Day] = &[
+                    $(
+                        $crate::registry::Day {
+                            year: $year,
+                            day: $day,
+                            title: $title,
+                            solve: $crate::solution::solve::<$module::Solver>,
+                            parse: $crate::solution::parse_any::<$module::Solver>,
+                            part1: $crate::solution::part1_any::<$module::Solver>,
+                            part2: $crate::solution::part2_any::<$module::Solver>,
+                        },
+                    )+
+                ];
+
+                #[cfg(test)]
+                mod example_tests {
+                    $(
+                        #[test]
+                        $(#[$attr])*
+                        fn $module() {
+                            $crate::registry::check_examples::<super::$module::Solver>(
+                                $year, $day,
+                            );
+                        }
+                    )+
+                }
+            }
+        )+
+
+        // Every registered year, in order.
+        pub static YEARS: &[&[$crate::registry::Day]] = &[$($year_module::DAYS),+];
+    };
+}
+
+pub fn years() -> impl Iterator<Item = u16> {
+    crate::YEARS.iter().map(|days| days[0].year)
+}
+
+pub fn latest_year() -> u16 {
+    years().max().unwrap()
+}
+
+pub fn year(year: u16) -> Option<&'static [Day]> {
+    crate::YEARS
+        .iter()
+        .copied()
+        .find(|days| days[0].year == year)
+}
+
+pub fn get_in(year: u16, day: usize) -> Option<&'static Day> {
+    self::year(year)?.iter().find(|d| d.day == day)
+}
+
+// Solves both parts of `day` of `year` on `input`, with the params and cancellation of `ctx`.
+pub fn run_in(year: u16, day: usize, input: &str, ctx: &Context) -> Result<Answers> {
+    get_in(year, day)
+        .ok_or_else(|| anyhow!("no solution for day {day} of {year}"))?
+        .run(input, ctx)
+}
+
+// The year `days`, `get` and `run` look days up in.
+pub const DEFAULT_YEAR: u16 = 2022;
+
+// All days of the default year, in order.
+pub fn days() -> &'static [Day] {
+    year(DEFAULT_YEAR).unwrap()
+}
+
+pub fn get(day: usize) -> Option<&'static Day> {
+    get_in(DEFAULT_YEAR, day)
+}
+
+// Solves both parts of `day` of the default year on `input`, with the params of the real
+// inputs.
+pub fn run(day: usize, input: &str) -> Result<Answers> {
+    run_in(DEFAULT_YEAR, day, input, &Context::default())
+}
+
+// Runs the parts that have an expected answer on every example input of the day.
+#[cfg(test)]
+pub(crate) fn check_examples<S: crate::solution::Solution>(year: u16, day: usize) {
+    use crate::answers::{answers_path, example_inputs, input_dir, input_name, Manifest};
+    use std::path::Path;
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let manifest = Manifest::load(&root.join(answers_path(year))).unwrap();
+    let mut checked = 0;
+    for path in example_inputs(&root.join(input_dir(year)), day).unwrap() {
+        let name = input_name(&path);
+        let Some(expected) = manifest.expected(day, &name) else {
+            continue;
+        };
+        let input = S::parse(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let ctx = Context::default().with_params(expected.params.clone());
+        if let Some(part1) = &expected.part1 {
+            assert_eq!(part1, &S::part1(&input, &ctx).unwrap(), "{name} part 1");
+        }
+        if let Some(part2) = &expected.part2 {
+            assert_eq!(part2, &S::part2(&input, &ctx).unwrap(), "{name} part 2");
+        }
+        checked += 1;
+    }
+    assert!(
+        checked > 0,
+        "no expected answers for day {day} of {year} examples"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selection::DAYS;
+
+    #[test]
+    fn registry_test() {
+        assert_eq!(DAYS, days().len());
+        assert!(days().iter().enumerate().all(|(i, d)| d.day == i + 1));
+        assert!(days().iter().all(|d| d.year == 2022));
+        assert_eq!("Monkey Map", get(22).unwrap().title);
+        assert_eq!("day22", get(22).unwrap().name());
+        assert!(get(0).is_none());
+        assert!(get(26).is_none());
+        assert_eq!(get(22).map(|d| d.title), get_in(2022, 22).map(|d| d.title));
+        assert!(get_in(2021, 1).is_none());
+        assert_eq!(DAYS, year(2022).unwrap().len());
+        assert!(year(2021).is_none());
+        assert_eq!(vec![2022], years().collect::<Vec<_>>());
+        assert_eq!(2022, latest_year());
+    }
+
+    #[test]
+    fn run_test() {
+        let answers = run(1, include_str!("../inputs/2022/day01_example.txt")).unwrap();
+        assert_eq!(
+            Answers {
+                part1: 24000.into(),
+                part2: 45000.into()
+            },
+            answers
+        );
+        assert!(run(26, "").is_err());
+        assert!(run_in(2021, 1, "", &Context::default()).is_err());
+
+        let example = include_str!("../inputs/2022/day15_example.txt");
+        let params = [("row".to_owned(), 10), ("max".to_owned(), 20)].into();
+        let answers = run_in(2022, 15, example, &Context::default().with_params(params)).unwrap();
+        assert_eq!(
+            Answers {
+                part1: 26.into(),
+                part2: 56000011.into()
+            },
+            answers
+        );
+    }
+
+    #[test]
+    fn phases_test() {
+        let day = get(1).unwrap();
+        let ctx = Context::default();
+        let input = (day.parse)(include_str!("../inputs/2022/day01_example.txt")).unwrap();
+        assert_eq!(Answer::Num(24000), (day.part1)(&*input, &ctx).unwrap());
+        assert!((get(2).unwrap().part1)(&*input, &ctx).is_err());
+    }
+}
@@ -0,0 +1,493 @@
+use anyhow::{anyhow, Result};
+use serde::{Serialize, Serializer};
+use std::fmt::{Display, Error, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::mem::Memory;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(anyhow!("unknown format '{s}', expected text, json or csv")),
+        }
+    }
+}
+
+fn nanos<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u128(d.as_nanos())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verification {
+    Ok,
+    Mismatch,
+    // No expected answers for this input or verification was skipped.
+    Unchecked,
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let s = match self {
+            Self::Ok => "ok",
+            Self::Mismatch => "mismatch",
+            Self::Unchecked => "unchecked",
+        };
+        f.pad(s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Sample {
+    #[serde(rename = "parse_ns", serialize_with = "nanos")]
+    pub parse: Duration,
+    #[serde(rename = "part1_ns", serialize_with = "nanos")]
+    pub part1: Duration,
+    #[serde(rename = "part2_ns", serialize_with = "nanos")]
+    pub part2: Duration,
+    // Both parts, parsing excluded.
+    #[serde(rename = "compute_ns", serialize_with = "nanos")]
+    pub compute: Duration,
+    #[serde(rename = "with_io_ns", serialize_with = "nanos")]
+    pub with_io: Duration,
+}
+
+// Distribution of the compute time of a day's samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", serialize_with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "mean_ns", serialize_with = "nanos")]
+    pub mean: Duration,
+    // Sample standard deviation, zero for a single sample.
+    #[serde(rename = "stddev_ns", serialize_with = "nanos")]
+    pub stddev: Duration,
+    // Nearest-rank 95th percentile.
+    #[serde(rename = "p95_ns", serialize_with = "nanos")]
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut samples = samples.to_vec();
+        samples.sort();
+        let n = samples.len() as f64;
+        let mean = samples.iter().map(|d| d.as_secs_f64()).sum::<f64>() / n;
+        let variance = if samples.len() > 1 {
+            samples
+                .iter()
+                .map(|d| (d.as_secs_f64() - mean).powi(2))
+                .sum::<f64>()
+                / (n - 1.0)
+        } else {
+            0.0
+        };
+        let p95_rank = (0.95 * n).ceil() as usize;
+        Some(Self {
+            min: samples[0],
+            median: median(&samples),
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            p95: samples[p95_rank - 1],
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DayRecord {
+    pub day: usize,
+    pub input: String,
+    pub part1: String,
+    pub part2: String,
+    pub verification: Verification,
+    // Fastest of the samples, same as reported by the text output.
+    #[serde(flatten)]
+    pub fastest: Sample,
+    pub compute_stats: Stats,
+    pub samples: Vec<Sample>,
+    // Of the first sample, only measured with --mem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Memory>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Totals {
+    #[serde(rename = "total_ns", serialize_with = "nanos")]
+    pub total: Duration,
+    #[serde(rename = "avg_ns", serialize_with = "nanos")]
+    pub avg: Duration,
+    #[serde(rename = "median_ns", serialize_with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "min_ns", serialize_with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "max_ns", serialize_with = "nanos")]
+    pub max: Duration,
+}
+
+fn median(array: &[Duration]) -> Duration {
+    if (array.len() % 2) == 0 {
+        let ind_left = array.len() / 2 - 1;
+        let ind_right = array.len() / 2;
+        (array[ind_left] + array[ind_right]).div_f64(2.0)
+    } else {
+        array[array.len() / 2]
+    }
+}
+
+impl Totals {
+    pub fn new(times: &[Duration]) -> Option<Self> {
+        if times.is_empty() {
+            return None;
+        }
+        let mut times = times.to_vec();
+        times.sort();
+        let total: Duration = times.iter().sum();
+        Some(Self {
+            total,
+            avg: total.div_f64(times.len() as f64),
+            median: median(&times),
+            min: times[0],
+            max: times[times.len() - 1],
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    // Input file missing or unreadable.
+    Input,
+    Parse,
+    // Error or panic in one of the parts.
+    Solution,
+    Mismatch,
+    TimedOut,
+}
+
+impl Display for FailureKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let s = match self {
+            Self::Input => "input",
+            Self::Parse => "parse",
+            Self::Solution => "solution",
+            Self::Mismatch => "mismatch",
+            Self::TimedOut => "timed out",
+        };
+        f.pad(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Failure {
+    pub day: usize,
+    // Input name, the file name without extension.
+    pub input: String,
+    pub kind: FailureKind,
+    pub message: String,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(
+            f,
+            "Day {:02}  {:<16}  {:<9}  {}",
+            self.day, self.input, self.kind, self.message
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub days: Vec<DayRecord>,
+    pub compute: Option<Totals>,
+    pub with_io: Option<Totals>,
+    // A day can both fail and have a record, e.g. on a mismatch. Days without any successful
+    // run only show up here.
+    pub failures: Vec<Failure>,
+}
+
+// One row per day, followed by rows named after the aggregates in the 'day' column.
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    day: String,
+    input: &'a str,
+    part1: &'a str,
+    part2: &'a str,
+    verification: Option<Verification>,
+    parse_ns: Option<u128>,
+    part1_ns: Option<u128>,
+    part2_ns: Option<u128>,
+    compute_ns: Option<u128>,
+    with_io_ns: Option<u128>,
+    median_ns: Option<u128>,
+    mean_ns: Option<u128>,
+    stddev_ns: Option<u128>,
+    p95_ns: Option<u128>,
+    // Space separated, in the order they were taken.
+    samples_compute_ns: String,
+    samples_with_io_ns: String,
+    allocations: Option<u64>,
+    allocated_bytes: Option<u64>,
+    peak_heap_bytes: Option<u64>,
+    resident_bytes: Option<u64>,
+}
+
+impl Report {
+    pub fn new(days: Vec<DayRecord>, failures: Vec<Failure>) -> Self {
+        let compute: Vec<Duration> = days.iter().map(|d| d.fastest.compute).collect();
+        let with_io: Vec<Duration> = days.iter().map(|d| d.fastest.with_io).collect();
+        Self {
+            compute: Totals::new(&compute),
+            with_io: Totals::new(&with_io),
+            days,
+            failures,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn to_csv(&self) -> Result<String> {
+        let join = |samples: &[Sample], f: fn(&Sample) -> Duration| {
+            samples
+                .iter()
+                .map(|s| f(s).as_nanos().to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let mut w = csv::Writer::from_writer(vec![]);
+        for d in &self.days {
+            w.serialize(CsvRow {
+                day: d.day.to_string(),
+                input: &d.input,
+                part1: &d.part1,
+                part2: &d.part2,
+                verification: Some(d.verification),
+                parse_ns: Some(d.fastest.parse.as_nanos()),
+                part1_ns: Some(d.fastest.part1.as_nanos()),
+                part2_ns: Some(d.fastest.part2.as_nanos()),
+                compute_ns: Some(d.fastest.compute.as_nanos()),
+                with_io_ns: Some(d.fastest.with_io.as_nanos()),
+                median_ns: Some(d.compute_stats.median.as_nanos()),
+                mean_ns: Some(d.compute_stats.mean.as_nanos()),
+                stddev_ns: Some(d.compute_stats.stddev.as_nanos()),
+                p95_ns: Some(d.compute_stats.p95.as_nanos()),
+                samples_compute_ns: join(&d.samples, |s| s.compute),
+                samples_with_io_ns: join(&d.samples, |s| s.with_io),
+                allocations: d.memory.map(|m| m.allocations),
+                allocated_bytes: d.memory.map(|m| m.allocated_bytes),
+                peak_heap_bytes: d.memory.map(|m| m.peak_heap_bytes),
+                resident_bytes: d.memory.map(|m| m.resident_bytes),
+            })?;
+        }
+        if let (Some(compute), Some(with_io)) = (self.compute, self.with_io) {
+            for (name, f) in [
+                ("total", (|t| t.total) as fn(&Totals) -> Duration),
+                ("avg", |t| t.avg),
+                ("median", |t| t.median),
+                ("min", |t| t.min),
+                ("max", |t| t.max),
+            ] {
+                w.serialize(CsvRow {
+                    day: name.to_owned(),
+                    input: "",
+                    part1: "",
+                    part2: "",
+                    verification: None,
+                    parse_ns: None,
+                    part1_ns: None,
+                    part2_ns: None,
+                    compute_ns: Some(f(&compute).as_nanos()),
+                    with_io_ns: Some(f(&with_io).as_nanos()),
+                    median_ns: None,
+                    mean_ns: None,
+                    stddev_ns: None,
+                    p95_ns: None,
+                    samples_compute_ns: String::new(),
+                    samples_with_io_ns: String::new(),
+                    allocations: None,
+                    allocated_bytes: None,
+                    peak_heap_bytes: None,
+                    resident_bytes: None,
+                })?;
+            }
+        }
+        Ok(String::from_utf8(w.into_inner()?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    fn record(day: usize, compute: Duration) -> DayRecord {
+        DayRecord {
+            day,
+            input: format!("inputs/day{day:02}"),
+            part1: "1".to_owned(),
+            part2: "a,b".to_owned(),
+            verification: Verification::Ok,
+            fastest: Sample {
+                parse: compute / 2,
+                part1: compute / 4,
+                part2: compute * 3 / 4,
+                compute,
+                with_io: compute * 2,
+            },
+            compute_stats: Stats::new(&[compute * 3, compute]).unwrap(),
+            samples: vec![
+                Sample {
+                    parse: compute,
+                    part1: compute,
+                    part2: compute * 2,
+                    compute: compute * 3,
+                    with_io: compute * 4,
+                },
+                Sample {
+                    parse: compute / 2,
+                    part1: compute / 4,
+                    part2: compute * 3 / 4,
+                    compute,
+                    with_io: compute * 2,
+                },
+            ],
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn totals_test() {
+        assert_eq!(None, Totals::new(&[]));
+        assert_eq!(
+            Some(Totals {
+                total: ms(10),
+                avg: ms(2),
+                median: ms(2),
+                min: ms(1),
+                max: ms(4),
+            }),
+            Totals::new(&[ms(4), ms(1), ms(2), ms(1), ms(2)])
+        );
+        assert_eq!(ms(3), Totals::new(&[ms(4), ms(2)]).unwrap().median);
+    }
+
+    #[test]
+    fn stats_test() {
+        assert_eq!(None, Stats::new(&[]));
+        assert_eq!(
+            Some(Stats {
+                min: ms(5),
+                median: ms(5),
+                mean: ms(5),
+                stddev: ms(0),
+                p95: ms(5),
+            }),
+            Stats::new(&[ms(5)])
+        );
+        let samples: Vec<Duration> = (1..=20).rev().map(ms).collect();
+        let stats = Stats::new(&samples).unwrap();
+        assert_eq!(ms(1), stats.min);
+        assert_eq!(Duration::from_micros(10500), stats.median);
+        assert_eq!(Duration::from_micros(10500), stats.mean);
+        assert_eq!(5916, stats.stddev.as_micros());
+        assert_eq!(ms(19), stats.p95);
+    }
+
+    #[test]
+    fn json_test() {
+        let mut with_memory = record(2, ms(3));
+        with_memory.memory = Some(Memory {
+            allocations: 3,
+            allocated_bytes: 4096,
+            peak_heap_bytes: 1024,
+            resident_bytes: 8192,
+        });
+        let report = Report::new(
+            vec![record(1, ms(1)), with_memory],
+            vec![Failure {
+                day: 16,
+                input: "day16".to_owned(),
+                kind: FailureKind::TimedOut,
+                message: "no result after 10s".to_owned(),
+            }],
+        );
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(2, json["days"][1]["day"]);
+        assert_eq!("ok", json["days"][1]["verification"]);
+        assert_eq!(3000000, json["days"][1]["compute_ns"]);
+        assert_eq!(2250000, json["days"][1]["part2_ns"]);
+        assert_eq!(9000000, json["days"][1]["samples"][0]["compute_ns"]);
+        assert_eq!(3000000, json["days"][1]["samples"][0]["parse_ns"]);
+        assert_eq!(6000000, json["days"][1]["compute_stats"]["median_ns"]);
+        assert_eq!(4000000, json["compute"]["total_ns"]);
+        assert_eq!(6000000, json["with_io"]["max_ns"]);
+        assert!(json["days"][0].get("memory").is_none());
+        assert_eq!(3, json["days"][1]["memory"]["allocations"]);
+        assert_eq!(1024, json["days"][1]["memory"]["peak_heap_bytes"]);
+        assert_eq!(8192, json["days"][1]["memory"]["resident_bytes"]);
+        assert_eq!(16, json["failures"][0]["day"]);
+        assert_eq!("timed_out", json["failures"][0]["kind"]);
+    }
+
+    #[test]
+    fn failure_test() {
+        let failure = Failure {
+            day: 9,
+            input: "day09_example2".to_owned(),
+            kind: FailureKind::Parse,
+            message: "parse failed: bad move".to_owned(),
+        };
+        assert_eq!(
+            "Day 09  day09_example2    parse      parse failed: bad move",
+            failure.to_string()
+        );
+    }
+
+    #[test]
+    fn csv_test() {
+        let mut with_memory = record(2, ms(1));
+        with_memory.memory = Some(Memory {
+            allocations: 3,
+            allocated_bytes: 4096,
+            peak_heap_bytes: 1024,
+            resident_bytes: 8192,
+        });
+        let report = Report::new(vec![record(1, ms(1)), with_memory], vec![]);
+        let csv = report.to_csv().unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            "day,input,part1,part2,verification,parse_ns,part1_ns,part2_ns,compute_ns,with_io_ns,median_ns,mean_ns,stddev_ns,p95_ns,samples_compute_ns,samples_with_io_ns,allocations,allocated_bytes,peak_heap_bytes,resident_bytes",
+            lines[0]
+        );
+        assert_eq!(
+            "1,inputs/day01,1,\"a,b\",ok,500000,250000,750000,1000000,2000000,2000000,2000000,1414214,3000000,3000000 1000000,4000000 2000000,,,,",
+            lines[1]
+        );
+        assert!(lines[2].ends_with(",4000000 2000000,3,4096,1024,8192"));
+        assert_eq!("total,,,,,,,,2000000,4000000,,,,,,,,,,", lines[3]);
+        assert_eq!(8, lines.len());
+    }
+}
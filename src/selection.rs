@@ -0,0 +1,156 @@
+use anyhow::{anyhow, bail, Context, Result};
+use std::fmt::{Display, Error, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::answers::input_name;
+
+pub const DAYS: usize = 25;
+
+// Days to run, given as a list of days and ranges like '1,3,10-15'. Sorted, without duplicates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Days(pub Vec<usize>);
+
+fn parse_day(s: &str) -> Result<usize> {
+    let day: usize = s
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid day '{s}'"))?;
+    if !(1..=DAYS).contains(&day) {
+        bail!("day {day} is not in 1-{DAYS}");
+    }
+    Ok(day)
+}
+
+impl FromStr for Days {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut days = vec![];
+        for part in s.split(',') {
+            let (first, last) = match part.split_once('-') {
+                Some((first, last)) => (parse_day(first)?, parse_day(last)?),
+                None => (parse_day(part)?, parse_day(part)?),
+            };
+            if first > last {
+                bail!("empty range '{part}'");
+            }
+            days.extend(first..=last);
+        }
+        days.sort_unstable();
+        days.dedup();
+        Ok(Self(days))
+    }
+}
+
+// Where the input of a day comes from, '-' is stdin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    // The real input of `day` in `dir`.
+    pub fn default_for(dir: &Path, day: usize) -> Self {
+        Self::File(dir.join(format!("day{day:02}")))
+    }
+
+    // Name answers are looked up by.
+    pub fn name(&self) -> String {
+        match self {
+            Self::File(path) => input_name(path),
+            Self::Stdin => "stdin".to_owned(),
+        }
+    }
+}
+
+impl FromStr for Source {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "" => Err(anyhow!("empty input path")),
+            "-" => Ok(Self::Stdin),
+            s => Ok(Self::File(PathBuf::from(s))),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "-"),
+        }
+    }
+}
+
+// Input of a single day, given as DAY=PATH.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputOverride {
+    pub day: usize,
+    pub source: Source,
+}
+
+impl FromStr for InputOverride {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (day, source) = s
+            .split_once('=')
+            .ok_or_else(|| anyhow!("expected DAY=PATH, got '{s}'"))?;
+        Ok(Self {
+            day: parse_day(day)?,
+            source: source.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_test() {
+        assert_eq!(Days(vec![5]), "5".parse().unwrap());
+        assert_eq!(
+            Days(vec![1, 3, 10, 11, 12, 13, 14, 15]),
+            "1,3,10-15".parse().unwrap()
+        );
+        assert_eq!(Days(vec![1, 2, 3]), "3,1-2,2".parse().unwrap());
+        assert!("0".parse::<Days>().is_err());
+        assert!("26".parse::<Days>().is_err());
+        assert!("5-3".parse::<Days>().is_err());
+        assert!("1,,2".parse::<Days>().is_err());
+        assert!("x".parse::<Days>().is_err());
+    }
+
+    #[test]
+    fn input_override_test() {
+        assert_eq!(
+            InputOverride {
+                day: 9,
+                source: Source::File(PathBuf::from("inputs/day09_example2.txt"))
+            },
+            "9=inputs/day09_example2.txt".parse().unwrap()
+        );
+        assert_eq!(
+            InputOverride {
+                day: 1,
+                source: Source::Stdin
+            },
+            "1=-".parse().unwrap()
+        );
+        assert!("inputs/day09".parse::<InputOverride>().is_err());
+        assert!("9=".parse::<InputOverride>().is_err());
+        assert!("30=inputs/day09".parse::<InputOverride>().is_err());
+    }
+
+    #[test]
+    fn source_test() {
+        assert_eq!("day01", Source::default_for(Path::new("inputs"), 1).name());
+        assert_eq!("stdin", Source::Stdin.name());
+        assert_eq!("-", Source::Stdin.to_string());
+    }
+}
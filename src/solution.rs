@@ -0,0 +1,321 @@
+use anyhow::{anyhow, Context as _, Result};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Error, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::progress::{Progress, ProgressSink};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Num(i64),
+    Text(String),
+    // Answers read off a rendered picture, like day 10's CRT.
+    Multiline(String),
+    // Day 25 has no second puzzle.
+    Empty,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Self::Num(n) => write!(f, "{n}"),
+            Self::Text(s) | Self::Multiline(s) => write!(f, "{s}"),
+            Self::Empty => Ok(()),
+        }
+    }
+}
+
+macro_rules! answer_from_num {
+    ($($t: ty),+) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Self::Num(n as i64)
+                }
+            }
+        )+
+    };
+}
+
+answer_from_num!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::Text(s.to_owned())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Answer,
+    pub part2: Answer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+// Named numbers some puzzles give next to the input, like the row day 15 asks about, which
+// differ between the examples and the real inputs.
+pub type Params = BTreeMap<String, i64>;
+
+// Passed to solutions by whoever runs them. Clones share the cancellation flag, so the runner
+// can keep one and hand the other to a worker.
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    cancelled: Arc<AtomicBool>,
+    progress: Option<Arc<dyn ProgressSink>>,
+    params: Arc<Params>,
+}
+
+impl Context {
+    pub fn with_progress(sink: Arc<dyn ProgressSink>) -> Self {
+        Self {
+            progress: Some(sink),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_params(self, params: Params) -> Self {
+        Self {
+            params: Arc::new(params),
+            ..self
+        }
+    }
+
+    pub fn param(&self, name: &str) -> Option<i64> {
+        self.params.get(name).copied()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    // Long searches call this every so often to give up once the runner is no longer
+    // interested in the result.
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(Cancelled.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    // Searches call this along with `check`, `progress` only runs if someone listens.
+    pub fn report(&self, progress: impl FnOnce() -> Progress) {
+        if let Some(sink) = &self.progress {
+            sink.report(&progress());
+        }
+    }
+}
+
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input, ctx: &Context) -> Result<Answer>;
+    fn part2(input: &Self::Input, ctx: &Context) -> Result<Answer>;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    // Time spent in both parts, parsing excluded.
+    pub fn compute(&self) -> Duration {
+        self.part1 + self.part2
+    }
+}
+
+// Attached as context to errors returned by `solve`, so callers can tell bad input apart
+// from a failing solution with `downcast_ref::<Phase>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Self::Parse => write!(f, "parse failed"),
+            Self::Part1 => write!(f, "part 1 failed"),
+            Self::Part2 => write!(f, "part 2 failed"),
+        }
+    }
+}
+
+// Returns answers for both parts and time it took to parse the input and compute each of them.
+pub fn solve<S: Solution>(input: &str, ctx: &Context) -> Result<(Answers, Timings)> {
+    let s = Instant::now();
+    let input = S::parse(input).context(Phase::Parse)?;
+    let parse = s.elapsed();
+
+    let s = Instant::now();
+    let part1 = S::part1(&input, ctx).context(Phase::Part1)?;
+    let part1_time = s.elapsed();
+
+    let s = Instant::now();
+    let part2 = S::part2(&input, ctx).context(Phase::Part2)?;
+    let part2_time = s.elapsed();
+
+    Ok((
+        Answers { part1, part2 },
+        Timings {
+            parse,
+            part1: part1_time,
+            part2: part2_time,
+        },
+    ))
+}
+
+// Parsed input of a day picked at runtime, for callers like benchmarks that time the phases
+// of `solve` separately.
+pub type AnyInput = Box<dyn Any>;
+
+pub fn parse_any<S: Solution>(input: &str) -> Result<AnyInput>
+where
+    S::Input: 'static,
+{
+    Ok(Box::new(S::parse(input)?))
+}
+
+fn downcast<S: Solution>(input: &dyn Any) -> Result<&S::Input>
+where
+    S::Input: 'static,
+{
+    input
+        .downcast_ref()
+        .ok_or_else(|| anyhow!("input was parsed by another day"))
+}
+
+pub fn part1_any<S: Solution>(input: &dyn Any, ctx: &Context) -> Result<Answer>
+where
+    S::Input: 'static,
+{
+    S::part1(downcast::<S>(input)?, ctx)
+}
+
+pub fn part2_any<S: Solution>(input: &dyn Any, ctx: &Context) -> Result<Answer>
+where
+    S::Input: 'static,
+{
+    S::part2(downcast::<S>(input)?, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn cancel_test() {
+        let ctx = Context::default();
+        let worker = ctx.clone();
+        assert!(worker.check().is_ok());
+        ctx.cancel();
+        assert!(worker.is_cancelled());
+        assert!(worker.check().unwrap_err().is::<Cancelled>());
+    }
+
+    struct Failing;
+
+    impl Solution for Failing {
+        type Input = usize;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.trim().parse()?)
+        }
+        fn part1(input: &Self::Input, _: &Context) -> Result<Answer> {
+            Ok((*input).into())
+        }
+        fn part2(_: &Self::Input, ctx: &Context) -> Result<Answer> {
+            ctx.check()?;
+            Ok(Answer::Empty)
+        }
+    }
+
+    #[test]
+    fn solve_phase_test() {
+        let ctx = Context::default();
+        let (answers, _) = solve::<Failing>("7", &ctx).unwrap();
+        assert_eq!(Answer::Num(7), answers.part1);
+
+        let e = solve::<Failing>("x", &ctx).unwrap_err();
+        assert_eq!(Some(&Phase::Parse), e.downcast_ref::<Phase>());
+        assert_eq!(
+            "parse failed: invalid digit found in string",
+            format!("{e:#}")
+        );
+
+        ctx.cancel();
+        let e = solve::<Failing>("7", &ctx).unwrap_err();
+        assert_eq!(Some(&Phase::Part2), e.downcast_ref::<Phase>());
+        assert!(e.is::<Cancelled>());
+    }
+
+    #[test]
+    fn any_test() {
+        let ctx = Context::default();
+        let input = parse_any::<Failing>("7").unwrap();
+        assert_eq!(Answer::Num(7), part1_any::<Failing>(&*input, &ctx).unwrap());
+        assert!(part1_any::<Failing>(&"7", &ctx).is_err());
+    }
+
+    #[derive(Debug, Default)]
+    struct Last(Mutex<Option<Progress>>);
+
+    impl ProgressSink for Last {
+        fn report(&self, progress: &Progress) {
+            *self.0.lock().unwrap() = Some(*progress);
+        }
+    }
+
+    #[test]
+    fn param_test() {
+        let ctx = Context::default().with_params([("row".to_owned(), 10)].into());
+        assert_eq!(Some(10), ctx.param("row"));
+        assert_eq!(None, ctx.param("max"));
+        assert_eq!(None, Context::default().param("row"));
+    }
+
+    #[test]
+    fn report_test() {
+        Context::default().report(|| unreachable!());
+        let sink = Arc::new(Last::default());
+        let ctx = Context::with_progress(sink.clone());
+        let progress = Progress {
+            states: 10,
+            frontier: 3,
+            best: Some(7),
+            pruned: 2,
+        };
+        ctx.clone().report(|| progress);
+        assert_eq!(Some(progress), *sink.0.lock().unwrap());
+    }
+}